@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+use chrono::Utc;
+use serde::Deserialize;
+use crate::tags;
+use crate::models::{AchievementInfo, Badge, Tag};
+
+/// Rules shipped with the binary, used when no config file is found.
+const DEFAULT_RULES: &str = include_str!("../../../config/achievements.json");
+/// Environment variable pointing at a rules file.
+const CONFIG_ENV: &str = "ACHIEVEMENTS_CONFIG";
+/// Rules file picked up from the working directory, next to `seed/`.
+const CONFIG_PATH: &str = "config/achievements.json";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Rule {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub condition: Condition,
+}
+
+/// What a learner has to reach to earn a badge.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Condition {
+    /// Answer `count` questions, optionally only correct ones or only within a tag
+    Answers {
+        count: u32,
+        tag: Option<String>,
+        #[serde(default)]
+        correct_only: bool,
+    },
+    FinishedAttempts { count: u32 },
+    PerfectAttempts { count: u32 },
+    /// Complete the daily challenge on `days` consecutive days
+    DailyStreak { days: u32 },
+    /// Reach `accuracy` over at least `min_answers` answers in every tag
+    TagsMastered { accuracy: f64, min_answers: u32 },
+}
+
+/// Something that happened in the solve/attempt flow.
+pub enum Event<'a> {
+    Answered { tags: &'a [String], correct: bool },
+    AttemptFinished { perfect: bool },
+    DailyCompleted { streak: u32 },
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Tally {
+    answers: u32,
+    correct: u32,
+}
+
+impl Tally {
+    fn add(&mut self, correct: bool) {
+        self.answers += 1;
+        if correct {
+            self.correct += 1;
+        }
+    }
+}
+
+/// Counters the rules are evaluated against, plus the badges earned so far.
+#[derive(Debug, Default)]
+pub struct LearnerAchievements {
+    overall: Tally,
+    by_tag: HashMap<String, Tally>,
+    finished_attempts: u32,
+    perfect_attempts: u32,
+    longest_streak: u32,
+    pub badges: Vec<Badge>,
+}
+
+impl LearnerAchievements {
+    /// Applies an event and awards badges for rules that are now met. Returns the new badges.
+    /// `all_tags` are the tags of all regular quizzes, which "every tag" rules are measured against.
+    pub fn record(&mut self, event: Event, rules: &[Rule], all_tags: &[Tag]) -> Vec<Badge> {
+        match event {
+            Event::Answered { tags, correct } => {
+                self.overall.add(correct);
+                for tag in tags {
+                    self.by_tag.entry(tag.clone()).or_default().add(correct);
+                }
+            }
+            Event::AttemptFinished { perfect } => {
+                self.finished_attempts += 1;
+                if perfect {
+                    self.perfect_attempts += 1;
+                }
+            }
+            Event::DailyCompleted { streak } => self.longest_streak = self.longest_streak.max(streak),
+        }
+
+        let now = Utc::now();
+        let earned: Vec<Badge> = rules.iter()
+            .filter(|rule| !self.badges.iter().any(|b| b.id == rule.id))
+            .filter(|rule| self.is_met(&rule.condition, all_tags))
+            .map(|rule| Badge {
+                id: rule.id.clone(),
+                name: rule.name.clone(),
+                description: rule.description.clone(),
+                awarded_at: now,
+            })
+            .collect();
+        self.badges.extend(earned.iter().cloned());
+        earned
+    }
+
+    fn is_met(&self, condition: &Condition, all_tags: &[Tag]) -> bool {
+        match condition {
+            Condition::Answers { count, tag, correct_only } => {
+                let tally = match tag {
+                    Some(tag) => self.by_tag.get(tag).copied().unwrap_or_default(),
+                    None => self.overall,
+                };
+                let answers = if *correct_only { tally.correct } else { tally.answers };
+                answers >= *count
+            }
+            Condition::FinishedAttempts { count } => self.finished_attempts >= *count,
+            Condition::PerfectAttempts { count } => self.perfect_attempts >= *count,
+            Condition::DailyStreak { days } => self.longest_streak >= *days,
+            Condition::TagsMastered { accuracy, min_answers } => !all_tags.is_empty() && all_tags.iter().all(|tag| {
+                self.by_tag.get(&tag.name).is_some_and(|t| {
+                    t.answers >= *min_answers && f64::from(t.correct) / f64::from(t.answers) >= *accuracy
+                })
+            }),
+        }
+    }
+}
+
+impl From<&Rule> for AchievementInfo {
+    fn from(rule: &Rule) -> Self {
+        AchievementInfo {
+            id: rule.id.clone(),
+            name: rule.name.clone(),
+            description: rule.description.clone(),
+        }
+    }
+}
+
+pub fn parse_rules(json: &str) -> Result<Vec<Rule>, serde_json::Error> {
+    let mut rules: Vec<Rule> = serde_json::from_str(json)?;
+    // Rule tags are compared with normalized quiz tags
+    for rule in &mut rules {
+        if let Condition::Answers { tag, .. } = &mut rule.condition {
+            *tag = tag.as_deref().and_then(tags::normalize);
+        }
+    }
+    Ok(rules)
+}
+
+/// Loads rules from `$ACHIEVEMENTS_CONFIG` or `config/achievements.json`, falling back
+/// to the built-in rules when neither is present or readable.
+pub fn load_rules() -> Vec<Rule> {
+    let path = std::env::var(CONFIG_ENV).unwrap_or_else(|_| CONFIG_PATH.to_string());
+    match std::fs::read_to_string(&path) {
+        Ok(json) => match parse_rules(&json) {
+            Ok(rules) => {
+                log::info!("Loaded {} achievement rules from {}", rules.len(), path);
+                return rules;
+            }
+            Err(e) => log::warn!("Invalid achievement rules in {}: {}", path, e),
+        },
+        Err(_) => log::info!("No achievement rules at {}, using built-in rules", path),
+    }
+    parse_rules(DEFAULT_RULES).expect("built-in achievement rules are valid")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(id: &str, condition: Condition) -> Rule {
+        Rule { id: id.to_string(), name: id.to_string(), description: String::new(), condition }
+    }
+
+    #[test]
+    fn built_in_rules_parse() {
+        let rules = parse_rules(DEFAULT_RULES).unwrap();
+        assert!(rules.iter().any(|r| matches!(r.condition, Condition::DailyStreak { days: 7 })));
+    }
+
+    #[test]
+    fn badges_are_awarded_once_when_conditions_are_met() {
+        let rules = [
+            rule("two_async", Condition::Answers { count: 2, tag: Some("async".to_string()), correct_only: true }),
+            rule("perfect", Condition::PerfectAttempts { count: 1 }),
+        ];
+        let tags = ["async".to_string()];
+        let no_tags = Vec::new();
+        let mut learner = LearnerAchievements::default();
+
+        assert!(learner.record(Event::Answered { tags: &tags, correct: true }, &rules, &no_tags).is_empty());
+        assert!(learner.record(Event::Answered { tags: &[], correct: true }, &rules, &no_tags).is_empty());
+        let earned = learner.record(Event::Answered { tags: &tags, correct: true }, &rules, &no_tags);
+        assert_eq!(earned.len(), 1);
+        assert_eq!(earned[0].id, "two_async");
+        assert!(learner.record(Event::Answered { tags: &tags, correct: true }, &rules, &no_tags).is_empty());
+
+        assert!(learner.record(Event::AttemptFinished { perfect: false }, &rules, &no_tags).is_empty());
+        assert_eq!(learner.record(Event::AttemptFinished { perfect: true }, &rules, &no_tags).len(), 1);
+        assert_eq!(learner.badges.len(), 2);
+    }
+
+    #[test]
+    fn mastery_requires_every_known_tag() {
+        let rules = [rule("master", Condition::TagsMastered { accuracy: 0.5, min_answers: 1 })];
+        let mut all_tags = Vec::new();
+        tags::register(&mut all_tags, &["a".to_string(), "b".to_string()]);
+        let mut learner = LearnerAchievements::default();
+
+        let a = ["a".to_string()];
+        let b = ["b".to_string()];
+        assert!(learner.record(Event::Answered { tags: &a, correct: true }, &rules, &all_tags).is_empty());
+        assert!(learner.record(Event::Answered { tags: &b, correct: false }, &rules, &all_tags).is_empty());
+        assert_eq!(learner.record(Event::Answered { tags: &b, correct: true }, &rules, &all_tags).len(), 1);
+    }
+}
@@ -0,0 +1,108 @@
+use crate::models::{Difficulty, Question, Quiz};
+
+/// Rating every learner starts with.
+pub const INITIAL_ABILITY: f64 = 1500.0;
+/// How far a single answer moves the learner's ability.
+const LEARNER_K: f64 = 32.0;
+/// How far a single answer moves a fresh question's rating.
+const QUESTION_K: f64 = 32.0;
+/// After this many answers a question's rating moves at half speed.
+const CALIBRATION_ANSWERS: f64 = 20.0;
+/// Chance of answering correctly that practice sessions aim for.
+pub const TARGET_SUCCESS: f64 = 0.7;
+
+/// Elo-style difficulty of a question, calibrated from every answer it receives.
+#[derive(Debug, Clone, Copy)]
+pub struct QuestionRating {
+    pub rating: f64,
+    pub answers: u32,
+    pub correct: u32,
+}
+
+impl QuestionRating {
+    /// Starting rating derived from the declared difficulty.
+    pub fn prior(difficulty: Option<Difficulty>) -> Self {
+        let rating = match difficulty {
+            Some(Difficulty::Beginner) => 1300.0,
+            Some(Difficulty::Intermediate) | None => 1500.0,
+            Some(Difficulty::Advanced) => 1700.0,
+        };
+        QuestionRating { rating, answers: 0, correct: 0 }
+    }
+}
+
+/// Probability that a learner of `ability` answers a question of `rating` correctly.
+pub fn expected_success(ability: f64, rating: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((rating - ability) / 400.0))
+}
+
+/// Updates both ratings after an answer and returns the learner's new ability.
+/// Questions settle as they collect answers, learners keep adapting.
+pub fn rate(ability: f64, question: &mut QuestionRating, correct: bool) -> f64 {
+    let outcome = if correct { 1.0 } else { 0.0 };
+    let surprise = outcome - expected_success(ability, question.rating);
+    let question_k = QUESTION_K * CALIBRATION_ANSWERS / (CALIBRATION_ANSWERS + f64::from(question.answers));
+
+    question.rating -= question_k * surprise;
+    question.answers += 1;
+    if correct {
+        question.correct += 1;
+    }
+    ability + LEARNER_K * surprise
+}
+
+/// Question rating at which the learner's expected success equals `TARGET_SUCCESS`.
+pub fn target_rating(ability: f64) -> f64 {
+    ability + 400.0 * (1.0 / TARGET_SUCCESS - 1.0).log10()
+}
+
+/// Picks the candidate whose rating is closest to the learner's target.
+pub fn pick_next<'a>(
+    candidates: impl IntoIterator<Item = (&'a Quiz, &'a Question, f64)>,
+    ability: f64,
+) -> Option<(&'a Quiz, &'a Question, f64)> {
+    let target = target_rating(ability);
+    candidates.into_iter()
+        .min_by(|a, b| (a.2 - target).abs().total_cmp(&(b.2 - target).abs()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    #[test]
+    fn answers_move_ability_and_difficulty_in_opposite_directions() {
+        let mut rating = QuestionRating::prior(None);
+        let ability = rate(INITIAL_ABILITY, &mut rating, true);
+        assert!((ability - 1516.0).abs() < 1e-9);
+        assert!((rating.rating - 1484.0).abs() < 1e-9);
+
+        let ability = rate(ability, &mut rating, false);
+        assert!(ability < 1516.0);
+        assert!(rating.rating > 1484.0);
+        assert_eq!((rating.answers, rating.correct), (2, 1));
+    }
+
+    #[test]
+    fn calibrated_questions_move_less() {
+        let mut fresh = QuestionRating::prior(None);
+        let mut settled = QuestionRating { answers: 200, ..QuestionRating::prior(None) };
+        rate(INITIAL_ABILITY, &mut fresh, true);
+        rate(INITIAL_ABILITY, &mut settled, true);
+        assert!((1500.0 - settled.rating) < (1500.0 - fresh.rating) / 5.0);
+    }
+
+    #[test]
+    fn picks_the_question_closest_to_the_target_success_rate() {
+        let quiz = fixtures::quiz((0..3).map(|_| fixtures::question("Q", Vec::new())).collect());
+        let ratings = [1300.0, 1350.0, 1700.0];
+        let candidates = quiz.questions.iter().zip(ratings).map(|(q, r)| (&quiz, q, r));
+
+        // Target for a 1500 learner is ~1353
+        let (_, picked, rating) = pick_next(candidates, INITIAL_ABILITY).unwrap();
+        assert_eq!(picked.id, quiz.questions[1].id);
+        assert_eq!(rating, 1350.0);
+        assert!((expected_success(INITIAL_ABILITY, target_rating(INITIAL_ABILITY)) - TARGET_SUCCESS).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,114 @@
+use std::collections::HashSet;
+use crate::id::Id;
+use crate::models::{Category, CategoryNode};
+
+/// The category and every category below it.
+pub fn descendants(categories: &[Category], id: Id) -> HashSet<Id> {
+    let mut found = HashSet::from([id]);
+    let mut frontier = vec![id];
+    while let Some(parent) = frontier.pop() {
+        for child in categories.iter().filter(|c| c.parent_id == Some(parent)) {
+            if found.insert(child.id) {
+                frontier.push(child.id);
+            }
+        }
+    }
+    found
+}
+
+/// Categories from the root down to `id`, or empty if `id` is unknown.
+pub fn breadcrumb(categories: &[Category], id: Id) -> Vec<Category> {
+    let mut path = Vec::new();
+    let mut next = Some(id);
+    while let Some(id) = next {
+        // Parents are checked on write, but never loop on a broken tree
+        if path.iter().any(|c: &Category| c.id == id) {
+            break;
+        }
+        let Some(category) = categories.iter().find(|c| c.id == id) else { break };
+        path.push(category.clone());
+        next = category.parent_id;
+    }
+    path.reverse();
+    path
+}
+
+/// Whether moving `id` under `parent` would make it its own ancestor.
+pub fn creates_cycle(categories: &[Category], id: Id, parent: Id) -> bool {
+    descendants(categories, id).contains(&parent)
+}
+
+/// Nests categories under their parents, each level sorted by name.
+pub fn tree(categories: &[Category]) -> Vec<CategoryNode> {
+    let known: HashSet<Id> = categories.iter().map(|c| c.id).collect();
+    let roots = categories.iter()
+        .filter(|c| c.parent_id.is_none_or(|parent| !known.contains(&parent)))
+        .map(|c| c.id);
+    nodes(categories, roots.collect())
+}
+
+fn nodes(categories: &[Category], ids: Vec<Id>) -> Vec<CategoryNode> {
+    let mut nodes: Vec<CategoryNode> = categories.iter()
+        .filter(|c| ids.contains(&c.id))
+        .map(|c| {
+            let children = categories.iter().filter(|child| child.parent_id == Some(c.id)).map(|child| child.id).collect();
+            CategoryNode { id: c.id, name: c.name.clone(), children: nodes(categories, children) }
+        })
+        .collect();
+    nodes.sort_by_key(|n| n.name.to_lowercase());
+    nodes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn category(name: &str, parent: Option<&Category>) -> Category {
+        Category { id: Id::new(), name: name.to_string(), parent_id: parent.map(|p| p.id) }
+    }
+
+    fn sample() -> Vec<Category> {
+        let js = category("JavaScript", None);
+        let async_ = category("Async", Some(&js));
+        let promises = category("Promises", Some(&async_));
+        let dom = category("DOM", Some(&js));
+        let rust = category("Rust", None);
+        vec![js, async_, promises, dom, rust]
+    }
+
+    #[test]
+    fn descendants_include_the_whole_subtree() {
+        let categories = sample();
+        let found = descendants(&categories, categories[0].id);
+        assert_eq!(found.len(), 4);
+        assert!(!found.contains(&categories[4].id));
+        assert_eq!(descendants(&categories, categories[2].id), HashSet::from([categories[2].id]));
+    }
+
+    #[test]
+    fn breadcrumb_runs_from_the_root() {
+        let categories = sample();
+        let names: Vec<String> = breadcrumb(&categories, categories[2].id).into_iter().map(|c| c.name).collect();
+        assert_eq!(names, ["JavaScript", "Async", "Promises"]);
+        assert!(breadcrumb(&categories, Id::new()).is_empty());
+    }
+
+    #[test]
+    fn detects_cycles() {
+        let categories = sample();
+        assert!(creates_cycle(&categories, categories[0].id, categories[2].id));
+        assert!(creates_cycle(&categories, categories[1].id, categories[1].id));
+        assert!(!creates_cycle(&categories, categories[3].id, categories[1].id));
+    }
+
+    #[test]
+    fn tree_nests_and_sorts_children() {
+        let categories = sample();
+        let roots = tree(&categories);
+        let names: Vec<&str> = roots.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(names, ["JavaScript", "Rust"]);
+        let children: Vec<&str> = roots[0].children.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(children, ["Async", "DOM"]);
+        assert_eq!(roots[0].children[0].children[0].name, "Promises");
+    }
+}
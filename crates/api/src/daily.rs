@@ -0,0 +1,131 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use chrono::{DateTime, Datelike, Days, FixedOffset, NaiveDate, Utc};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use crate::id::Id;
+use crate::models::{Attempt, Question, Quiz};
+use crate::selection::{draw_questions, question_pool, source_tags, QuestionFilter};
+
+/// Number of questions in each daily challenge.
+pub const DAILY_QUESTION_COUNT: usize = 5;
+
+/// Parses a timezone given as a fixed UTC offset (`+02:00`, `-05:30`) or `UTC`/`Z`.
+/// An unsigned offset is taken as positive, since an unencoded `+` arrives as a space.
+pub fn parse_offset(tz: Option<&str>) -> Option<FixedOffset> {
+    let tz = tz.map(str::trim).unwrap_or("UTC");
+    if tz.is_empty() || tz.eq_ignore_ascii_case("utc") || tz.eq_ignore_ascii_case("z") {
+        return FixedOffset::east_opt(0);
+    }
+    if tz.starts_with(['+', '-']) {
+        tz.parse().ok()
+    } else {
+        format!("+{}", tz).parse().ok()
+    }
+}
+
+/// The calendar date at `now` in the given timezone.
+pub fn today(offset: FixedOffset, now: DateTime<Utc>) -> NaiveDate {
+    now.with_timezone(&offset).date_naive()
+}
+
+/// Builds the challenge for a date. The same pool always yields the same questions for a date.
+pub fn challenge_quiz(quizzes: &[Quiz], date: NaiveDate) -> Option<Quiz> {
+    let pool = question_pool(quizzes, &QuestionFilter::default());
+    let mut rng = StdRng::seed_from_u64(date.num_days_from_ce() as u64);
+    let questions: Vec<Question> = draw_questions(&pool, DAILY_QUESTION_COUNT, &mut rng).into_iter().cloned().collect();
+    if questions.is_empty() {
+        return None;
+    }
+
+    Some(Quiz {
+        id: Id::new(),
+        title: format!("Daily Challenge {}", date),
+        category_id: None,
+        tags: source_tags(&pool, &questions),
+        questions,
+        difficulty: None,
+        reveal_policy: Default::default(),
+        time_limit_seconds: None,
+        exam_mode: false,
+        shuffle_options: true,
+        shuffle_questions: false,
+        generated: true,
+    })
+}
+
+/// Dates on which the learner's challenge attempt was finished or ran out of time;
+/// like leaderboards and achievements, an expired attempt counts as completed.
+pub fn completed_days(dates: &BTreeMap<NaiveDate, Id>, attempts: &HashMap<Id, Attempt>) -> BTreeSet<NaiveDate> {
+    dates.iter()
+        .filter(|(_, id)| attempts.get(id).is_some_and(Attempt::is_finished))
+        .map(|(date, _)| *date)
+        .collect()
+}
+
+/// Current and longest runs of consecutive completed days. The current streak
+/// stays alive until the end of the day after the last completion.
+pub fn streaks(completed: &BTreeSet<NaiveDate>, today: NaiveDate) -> (u32, u32) {
+    let mut longest = 0;
+    let mut run = 0;
+    let mut previous: Option<NaiveDate> = None;
+    for &date in completed {
+        run = match previous {
+            Some(prev) if prev.checked_add_days(Days::new(1)) == Some(date) => run + 1,
+            _ => 1,
+        };
+        longest = longest.max(run);
+        previous = Some(date);
+    }
+
+    let yesterday = today.checked_sub_days(Days::new(1));
+    let current = match previous {
+        Some(last) if last == today || Some(last) == yesterday => run,
+        _ => 0,
+    };
+    (current, longest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::AttemptStatus;
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_fixed_offsets() {
+        assert_eq!(parse_offset(None).unwrap().local_minus_utc(), 0);
+        assert_eq!(parse_offset(Some("UTC")).unwrap().local_minus_utc(), 0);
+        assert_eq!(parse_offset(Some("-05:30")).unwrap().local_minus_utc(), -(5 * 3600 + 1800));
+        assert_eq!(parse_offset(Some(" 02:00")).unwrap().local_minus_utc(), 7200);
+        assert!(parse_offset(Some("Europe/Berlin")).is_none());
+
+        let now = DateTime::parse_from_rfc3339("2024-05-16T23:30:00Z").unwrap().with_timezone(&Utc);
+        assert_eq!(today(parse_offset(Some("+02:00")).unwrap(), now), date("2024-05-17"));
+    }
+
+    #[test]
+    fn streaks_count_consecutive_days() {
+        let completed: BTreeSet<NaiveDate> = ["2024-05-01", "2024-05-02", "2024-05-03", "2024-05-07", "2024-05-08"]
+            .into_iter().map(date).collect();
+        assert_eq!(streaks(&completed, date("2024-05-08")), (2, 3));
+        assert_eq!(streaks(&completed, date("2024-05-09")), (2, 3));
+        assert_eq!(streaks(&completed, date("2024-05-10")), (0, 3));
+        assert_eq!(streaks(&BTreeSet::new(), date("2024-05-10")), (0, 0));
+    }
+
+    #[test]
+    fn expired_attempts_complete_their_day() {
+        let quiz = crate::fixtures::quiz(Vec::new());
+        let mut attempts = HashMap::new();
+        let mut dates = BTreeMap::new();
+        for (day, status) in [("2024-05-01", AttemptStatus::Finished), ("2024-05-02", AttemptStatus::Expired), ("2024-05-03", AttemptStatus::InProgress)] {
+            let attempt = Attempt { status, ..Attempt::new(&quiz, None) };
+            dates.insert(date(day), attempt.id);
+            attempts.insert(attempt.id, attempt);
+        }
+        assert_eq!(completed_days(&dates, &attempts), [date("2024-05-01"), date("2024-05-02")].into());
+    }
+}
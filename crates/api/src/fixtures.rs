@@ -0,0 +1,44 @@
+use crate::id::Id;
+use crate::models::{Question, QuestionOption, Quiz};
+
+/// A quiz titled "Quiz" with the given questions and default settings.
+/// Tests override what they need with struct update syntax or by assignment.
+pub fn quiz(questions: Vec<Question>) -> Quiz {
+    Quiz {
+        id: Id::new(),
+        title: "Quiz".to_string(),
+        category_id: None,
+        questions,
+        tags: Vec::new(),
+        difficulty: None,
+        reveal_policy: Default::default(),
+        time_limit_seconds: None,
+        exam_mode: false,
+        shuffle_options: true,
+        shuffle_questions: false,
+        generated: false,
+    }
+}
+
+/// A question with the given text and options and nothing else set.
+pub fn question(text: &str, options: Vec<QuestionOption>) -> Question {
+    Question {
+        id: Id::new(),
+        text: text.to_string(),
+        options,
+        explanation: None,
+        difficulty: None,
+        estimated_time_seconds: None,
+        time_limit_seconds: None,
+        hints: Vec::new(),
+    }
+}
+
+pub fn option(text: &str, is_correct: bool) -> QuestionOption {
+    QuestionOption {
+        id: Id::new(),
+        text: text.to_string(),
+        is_correct,
+        description: None,
+    }
+}
@@ -1,13 +1,41 @@
-use actix_web::{web, HttpResponse, Responder};
+use actix_web::{http::header, web, HttpRequest, HttpResponse, Responder};
+use actix_web::error::{InternalError, JsonPayloadError, QueryPayloadError};
 use crate::models::{
     CreateQuizRequest, Quiz, Question, QuestionOption, 
-    SubmitAnswerRequest, AnswerResponse,
-    Category, CreateCategoryRequest, UpdateQuizRequest, Tag,
-    PaginationParams, ErrorResponse,
+    SubmitAnswerRequest, AnswerResponse, HintResponse, RevealedOption,
+    Attempt, AttemptAnswer, AnswerRecord, AttemptAnswerRequest, AttemptStatus, StartAttemptRequest,
+    Category, CreateCategoryRequest, UpdateCategoryRequest, UpdateQuizRequest,
+    CreateQuestionRequest, CreateOptionRequest, UpdateQuestionRequest, UpdateOptionRequest, ReorderRequest,
+    PaginationParams, ErrorResponse, Difficulty, QuizSort, SortOrder,
+    GenerateQuizRequest, RandomQuestionResponse,
+    ReviewGradeRequest, ReviewItem, ReviewQueue,
+    StartPracticeRequest, PracticeSession, PracticeQuestion, PracticeAnswerRequest, PracticeAnswerResponse,
+    Leaderboard, LeaderboardEntry, LeaderboardWindow,
+    DailyChallenge, StartDailyRequest, DailyStreak,
+    AchievementInfo, AchievementsReport, SearchResults, FieldError, ValidationErrorResponse,
+    TagMode, TagUsage, Page, QuizView, QuizSummary,
 };
-use crate::state::AppState;
+use crate::state::{AppState, learner_key};
 use crate::id::Id;
-use rand::Rng;
+use crate::scoring::{question_score, score_attempt};
+use crate::shuffle::attempt_questions;
+use crate::leaderboard;
+use crate::daily;
+use crate::tags;
+use crate::categories;
+use crate::patch::{self, PatchError};
+use crate::validation::{self, Validate};
+use crate::pagination::{link_header, paginate, PageLinks};
+use crate::search::{self as full_text, SearchIndex};
+use crate::progress::build_report;
+use crate::adaptive::{self, QuestionRating};
+use crate::selection::{draw_questions, pick_quiz, question_pool, source_tags, QuestionFilter};
+use chrono::{Duration, FixedOffset, NaiveDate, Utc};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
 
 #[utoipa::path(
     get,
@@ -28,6 +56,8 @@ pub async fn health_check() -> impl Responder {
     tag = "Management",
     responses(
         (status = 201, description = "Quiz created", body = Quiz),
+        (status = 400, description = "Unknown category", body = ErrorResponse),
+        (status = 422, description = "Invalid fields", body = ValidationErrorResponse),
         (status = 500, description = "Internal Server Error")
     )
 )]
@@ -35,39 +65,38 @@ pub async fn create_quiz(
     data: web::Data<AppState>,
     req: web::Json<CreateQuizRequest>,
 ) -> impl Responder {
+    if let Err(response) = check_valid(&*req) {
+        return response;
+    }
     let mut quizzes = match data.quizzes.write() {
         Ok(q) => q,
         Err(_) => return HttpResponse::InternalServerError().body("Lock poisoned"),
     };
+    if let Err(response) = check_category(&data, req.category_id) {
+        return response;
+    }
 
     let quiz_id = Id::new();
-    let questions = req.questions.iter().map(|q| {
-        let q_id = Id::new();
-        let options = q.options.iter().map(|o| {
-            QuestionOption {
-                id: Id::new(),
-                text: o.text.clone(),
-                is_correct: o.is_correct,
-                description: o.description.clone(),
-            }
-        }).collect();
-        Question {
-            id: q_id,
-            text: q.text.clone(),
-            options,
-            explanation: q.explanation.clone(),
-        }
-    }).collect();
+    let questions = req.questions.iter().map(Question::from).collect();
 
     let new_quiz = Quiz {
         id: quiz_id,
         title: req.title.clone(),
         category_id: req.category_id,
         questions,
-        tags: req.tags.clone().unwrap_or_default(),
+        tags: tags::normalize_all(req.tags.iter().flatten()),
+        difficulty: req.difficulty,
+        reveal_policy: req.reveal_policy.unwrap_or_default(),
+        time_limit_seconds: req.time_limit_seconds,
+        exam_mode: req.exam_mode.unwrap_or(false),
+        shuffle_options: req.shuffle_options.unwrap_or(true),
+        shuffle_questions: req.shuffle_questions.unwrap_or(false),
+        generated: false,
     };
 
     quizzes.push(new_quiz.clone());
+    data.register_tags(&new_quiz.tags);
+    data.reindex_questions(&quizzes);
 
     HttpResponse::Created().json(new_quiz)
 }
@@ -96,7 +125,7 @@ pub async fn get_quiz(
 
     let quiz_id = path.into_inner();
     if let Some(quiz) = quizzes.iter().find(|q| q.id == quiz_id) {
-        HttpResponse::Ok().json(quiz)
+        HttpResponse::Ok().json(learner_view(quiz))
     } else {
         HttpResponse::NotFound().body("Quiz not found")
     }
@@ -105,9 +134,121 @@ pub async fn get_quiz(
 #[derive(serde::Deserialize, utoipa::IntoParams)]
 pub struct ListQuizzesFilter {
     category_id: Option<Id>,
+    /// With `category_id`, also list quizzes in its subcategories
+    pub include_descendants: Option<bool>,
     pub exclude_ids: Option<String>,
+    /// Only quizzes with exactly this difficulty
+    #[param(inline)]
+    pub difficulty: Option<Difficulty>,
+    /// Only quizzes whose total estimated time (seconds) fits within this limit
+    pub max_estimated_time: Option<u32>,
+    /// Comma-separated tags to filter by
+    pub tags: Option<String>,
+    /// Whether quizzes need `any` (default) or `all` of `tags`
+    #[param(inline)]
+    pub tag_mode: Option<TagMode>,
+    /// Comma-separated tags; quizzes carrying any of them are left out
+    pub exclude_tags: Option<String>,
+    #[param(inline)]
+    pub sort: Option<QuizSort>,
+    #[param(inline)]
+    pub order: Option<SortOrder>,
+    /// `full` quizzes (default) or `summary` objects without questions
+    #[param(inline)]
+    pub view: Option<QuizView>,
+    /// Comma-separated quiz fields to return instead of a view, e.g. `id,title,question_count`
+    pub fields: Option<String>,
+    /// 1-based page number (default 1)
     pub page: Option<u32>,
+    /// Items per page (default 10, max 100)
     pub per_page: Option<u32>,
+    /// Continue after this quiz ID instead of using `page`; only in creation order
+    pub cursor: Option<Id>,
+}
+
+/// Fields that `fields` can pick from a quiz; the last two are computed.
+const QUIZ_FIELDS: &[&str] = &[
+    "id", "title", "category_id", "questions", "tags", "difficulty", "reveal_policy",
+    "time_limit_seconds", "exam_mode", "shuffle_options", "shuffle_questions",
+    "question_count", "estimated_time_seconds",
+];
+
+/// Parses the `fields` parameter, rejecting names that aren't quiz fields.
+fn parse_fields(fields: &str) -> Result<Vec<String>, String> {
+    let fields: Vec<String> = fields.split(',')
+        .map(str::trim)
+        .filter(|f| !f.is_empty())
+        .map(String::from)
+        .collect();
+    if let Some(unknown) = fields.iter().find(|f| !QUIZ_FIELDS.contains(&f.as_str())) {
+        return Err(format!("Unknown field '{}'", unknown));
+    }
+    if fields.is_empty() {
+        return Err("fields must name at least one field".to_string());
+    }
+    Ok(fields)
+}
+
+fn select_fields(quiz: &Quiz, fields: &[String]) -> serde_json::Value {
+    let mut value = serde_json::to_value(quiz).unwrap_or_default();
+    if let serde_json::Value::Object(map) = &mut value {
+        map.insert("question_count".to_string(), quiz.questions.len().into());
+        map.insert("estimated_time_seconds".to_string(), quiz.estimated_time_seconds().into());
+        map.retain(|key, _| fields.contains(key));
+    }
+    value
+}
+
+/// Applies the sort direction to an ordering of present values.
+fn directed(ordering: Ordering, order: SortOrder) -> Ordering {
+    match order {
+        SortOrder::Asc => ordering,
+        SortOrder::Desc => ordering.reverse(),
+    }
+}
+
+/// Parses a comma-separated list of IDs, skipping invalid entries.
+fn parse_id_list(ids: Option<&str>) -> Vec<Id> {
+    ids.unwrap_or("")
+        .split(',')
+        .filter_map(|s| s.trim().parse().ok())
+        .collect()
+}
+
+/// Parses a comma-separated list of tags into their canonical form, skipping empty entries.
+fn parse_tag_list(names: Option<&str>) -> Vec<String> {
+    let names: Vec<String> = names.unwrap_or("").split(',').map(String::from).collect();
+    tags::normalize_all(&names)
+}
+
+fn matches_tags(quiz: &Quiz, tags: &[String], mode: TagMode, exclude: &[String]) -> bool {
+    if quiz.tags.iter().any(|t| exclude.contains(t)) {
+        return false;
+    }
+    if tags.is_empty() {
+        return true;
+    }
+    match mode {
+        TagMode::Any => tags.iter().any(|t| quiz.tags.contains(t)),
+        TagMode::All => tags.iter().all(|t| quiz.tags.contains(t)),
+    }
+}
+
+/// Orders optional sort keys, always placing missing values last.
+fn compare_optional<T: Ord>(a: Option<T>, b: Option<T>, order: SortOrder) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => directed(a.cmp(&b), order),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+fn matches_time_limit(quiz: &Quiz, max_estimated_time: Option<u32>) -> bool {
+    match max_estimated_time {
+        Some(max) => quiz.estimated_time_seconds().is_some_and(|t| t <= max),
+        None => true,
+    }
 }
 
 #[utoipa::path(
@@ -118,11 +259,14 @@ pub struct ListQuizzesFilter {
         ListQuizzesFilter
     ),
     responses(
-        (status = 200, description = "List Quizzes", body = Vec<Quiz>),
+        (status = 200, description = "List Quizzes; `view=summary` returns a QuizSummaryPage and `fields` only the chosen fields", body = QuizPage),
+        (status = 400, description = "Invalid paging parameters", body = ErrorResponse),
+        (status = 422, description = "Invalid query parameters", body = ValidationErrorResponse),
         (status = 500, description = "Internal Server Error")
     )
 )]
 pub async fn list_quizzes(
+    request: HttpRequest,
     data: web::Data<AppState>, 
     filter: web::Query<ListQuizzesFilter>,
 ) -> impl Responder {
@@ -131,38 +275,129 @@ pub async fn list_quizzes(
         Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
     };
 
-    let page = filter.page.unwrap_or(1);
-    let per_page = filter.per_page.unwrap_or(10);
-    
-    // Convert exclude_ids string to Vec<Id>
-    let exclude_ids: Vec<Id> = filter.exclude_ids.as_deref().unwrap_or("")
-        .split(',')
-        .filter_map(|s| s.trim().parse().ok())
-        .collect();
+    let fields = match filter.fields.as_deref().map(parse_fields).transpose() {
+        Ok(fields) => fields,
+        Err(error) => return HttpResponse::BadRequest().json(ErrorResponse { error }),
+    };
+    if fields.is_some() && filter.view.is_some() {
+        return HttpResponse::BadRequest().json(ErrorResponse { error: "Use either view or fields, not both".to_string() });
+    }
 
-    let filtered: Vec<Quiz> = quizzes.iter()
+    let category_ids: Option<HashSet<Id>> = match (filter.category_id, filter.include_descendants.unwrap_or(false)) {
+        (Some(id), true) => match data.categories.read() {
+            Ok(categories) => Some(categories::descendants(&categories, id)),
+            Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+        },
+        (Some(id), false) => Some(HashSet::from([id])),
+        (None, _) => None,
+    };
+    let exclude_ids = parse_id_list(filter.exclude_ids.as_deref());
+    let tags = parse_tag_list(filter.tags.as_deref());
+    let exclude_tags = parse_tag_list(filter.exclude_tags.as_deref());
+    let tag_mode = filter.tag_mode.unwrap_or_default();
+
+    let mut filtered: Vec<Quiz> = quizzes.iter()
         .filter(|q| {
-            if let Some(cat_id) = filter.category_id {
-                if q.category_id != Some(cat_id) { return false; }
-            }
-            if !exclude_ids.is_empty() {
-                if exclude_ids.contains(&q.id) { return false; }
-            }
-            true
+            if q.generated { return false; }
+            if let Some(ids) = &category_ids
+                && !q.category_id.is_some_and(|id| ids.contains(&id)) { return false; }
+            if exclude_ids.contains(&q.id) { return false; }
+            if !matches_tags(q, &tags, tag_mode, &exclude_tags) { return false; }
+            if filter.difficulty.is_some() && q.difficulty != filter.difficulty { return false; }
+            matches_time_limit(q, filter.max_estimated_time)
         })
-        .cloned()
+        .map(learner_view)
         .collect();
 
-    // Pagination
-    let total = filtered.len();
-    let start = ((page - 1) * per_page) as usize;
-    if start >= total {
-         return HttpResponse::Ok().json(Vec::<Quiz>::new());
+    // Creation order first, so equal sort keys keep a stable order
+    filtered.sort_by_key(|q| q.id.to_i64());
+    let order = filter.order.unwrap_or_default();
+    match filter.sort {
+        Some(QuizSort::Difficulty) => filtered.sort_by(|a, b| compare_optional(a.difficulty, b.difficulty, order)),
+        Some(QuizSort::EstimatedTime) => filtered.sort_by(|a, b| compare_optional(a.estimated_time_seconds(), b.estimated_time_seconds(), order)),
+        Some(QuizSort::Title) => filtered.sort_by(|a, b| directed(a.title.to_lowercase().cmp(&b.title.to_lowercase()), order)),
+        Some(QuizSort::QuestionCount) => filtered.sort_by(|a, b| directed(a.questions.len().cmp(&b.questions.len()), order)),
+        Some(QuizSort::Created) if order == SortOrder::Desc => filtered.reverse(),
+        Some(QuizSort::Created) | None => {}
+    }
+    // Cursors rely on ascending creation order
+    let in_creation_order = matches!(filter.sort, None | Some(QuizSort::Created)) && order == SortOrder::Asc;
+    let id_of: Option<fn(&Quiz) -> Id> = if in_creation_order { Some(|q| q.id) } else { None };
+
+    let paging = PaginationParams { page: filter.page, per_page: filter.per_page, cursor: filter.cursor };
+    let page = paginate(filtered, &paging, id_of, &page_links(&request));
+    match (fields, filter.view.unwrap_or_default()) {
+        (Some(fields), _) => paged_response(page.map(|page| page.map(|quiz| select_fields(&quiz, &fields)))),
+        (None, QuizView::Summary) => paged_response(page.map(|page| page.map(|quiz| QuizSummary::from(&quiz)))),
+        (None, QuizView::Full) => paged_response(page),
+    }
+}
+
+fn page_links(request: &HttpRequest) -> PageLinks {
+    PageLinks::new(request.path(), request.query_string())
+}
+
+/// Responds with a page and its `Link` header, or 400 for invalid paging parameters.
+fn paged_response<T: serde::Serialize>(page: Result<Page<T>, String>) -> HttpResponse {
+    match page {
+        Ok(page) => {
+            let mut response = HttpResponse::Ok();
+            if let Some(links) = link_header(&page) {
+                response.insert_header((header::LINK, links));
+            }
+            response.json(page)
+        }
+        Err(error) => HttpResponse::BadRequest().json(ErrorResponse { error }),
+    }
+}
+
+/// Clears explanations, which usually give the answer away; exam-mode quizzes
+/// serve their questions without them until the attempt is finished.
+fn withhold_explanations(questions: &mut [Question]) {
+    for question in questions {
+        question.explanation = None;
+    }
+}
+
+/// A quiz as served to learners outside a finished attempt.
+fn learner_view(quiz: &Quiz) -> Quiz {
+    let mut quiz = quiz.clone();
+    if quiz.exam_mode {
+        withhold_explanations(&mut quiz.questions);
+    }
+    quiz
+}
+
+/// Builds the response for a single answer, applying the quiz reveal policy.
+/// Exam-mode quizzes only acknowledge the answer; results come with the finished attempt.
+fn answer_response(quiz: &Quiz, question: &Question, option: &QuestionOption, hints_used: usize) -> AnswerResponse {
+    if quiz.exam_mode {
+        return AnswerResponse {
+            correct: None,
+            message: "Answer recorded.".to_string(),
+            explanation: None,
+            option_feedback: None,
+            correct_options: Vec::new(),
+            score: None,
+            hints_used,
+        };
+    }
+
+    let policy = quiz.reveal_policy;
+    let correct_options = if !option.is_correct && policy.reveals_correct_on_answer() {
+        question.options.iter().filter(|o| o.is_correct).map(RevealedOption::from).collect()
+    } else {
+        Vec::new()
+    };
+    AnswerResponse {
+        correct: Some(option.is_correct),
+        message: if option.is_correct { "Correct!".to_string() } else { "Incorrect.".to_string() },
+        explanation: question.explanation.clone(),
+        option_feedback: option.description.clone().filter(|_| policy.shows_option_feedback()),
+        correct_options,
+        score: Some(question_score(option.is_correct, hints_used)),
+        hints_used,
     }
-    let end = std::cmp::min(start + per_page as usize, total);
-    
-    let page_items = &filtered[start..end];
-    HttpResponse::Ok().json(page_items)
 }
 
 #[utoipa::path(
@@ -176,33 +411,69 @@ pub async fn list_quizzes(
     responses(
         (status = 200, description = "Answer result", body = AnswerResponse),
         (status = 400, description = "Invalid request"),
+        (status = 403, description = "Quiz is in exam mode", body = ErrorResponse),
+        (status = 404, description = "Quiz, question or option not found", body = ErrorResponse),
+        (status = 422, description = "Invalid fields", body = ValidationErrorResponse),
         (status = 500, description = "Internal Server Error")
     )
 )]
 pub async fn submit_answer(
     data: web::Data<AppState>,
-    _path: web::Path<Id>,
+    path: web::Path<Id>,
     req: web::Json<SubmitAnswerRequest>,
 ) -> impl Responder {
+    if let Err(response) = check_valid(&*req) {
+        return response;
+    }
     let quizzes = match data.quizzes.read() {
         Ok(q) => q,
         Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse{ error: "Lock poisoned".to_string() }),
     };
-
-    // Find question
-    for quiz in quizzes.iter() {
-        if let Some(question) = quiz.questions.iter().find(|q| q.id == req.question_id) {
-            if let Some(option) = question.options.iter().find(|o| o.id == req.option_id) {
-                return HttpResponse::Ok().json(AnswerResponse {
-                    correct: option.is_correct,
-                    message: if option.is_correct { "Correct!".to_string() } else { "Incorrect.".to_string() },
-                    explanation: question.explanation.clone(),
-                });
-            }
+    let quiz_id = path.into_inner();
+    // The index finds the quiz, and tells us up front whether the question belongs to it
+    let position = match data.question_index.read() {
+        Ok(index) => index.get(&req.question_id)
+            .and_then(|owners| owners.iter().find(|(id, _)| *id == quiz_id))
+            .map(|(_, position)| *position),
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse{ error: "Lock poisoned".to_string() }),
+    };
+    let Some(quiz) = position.and_then(|position| quizzes.get(position)) else {
+        if quizzes.iter().any(|q| q.id == quiz_id) {
+            return HttpResponse::NotFound().json(ErrorResponse{ error: "Question not found in quiz".to_string() });
         }
+        return HttpResponse::NotFound().json(ErrorResponse{ error: "Quiz not found".to_string() });
+    };
+    let Some(question) = quiz.questions.iter().find(|q| q.id == req.question_id) else {
+        return HttpResponse::NotFound().json(ErrorResponse{ error: "Question not found in quiz".to_string() });
+    };
+    let Some(option) = question.options.iter().find(|o| o.id == req.option_id) else {
+        return HttpResponse::NotFound().json(ErrorResponse{ error: "Option not found".to_string() });
+    };
+    if quiz.exam_mode {
+        return HttpResponse::Forbidden().json(ErrorResponse{ error: "Exam quizzes can only be answered within an attempt".to_string() });
+    }
+
+    // Revealed hints are charged to this answer only
+    let hints_used = match &req.user_email {
+        Some(email) => match data.hint_usage.write() {
+            Ok(mut usage) => usage.remove(&(learner_key(email), req.question_id)).unwrap_or(0),
+            Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse{ error: "Lock poisoned".to_string() }),
+        },
+        None => 0,
+    };
+
+    data.rate_answer(req.user_email.as_deref(), question.id, quiz.question_difficulty(question), option.is_correct);
+    if let Some(email) = &req.user_email {
+        data.record_answer(&quizzes, email, AnswerRecord {
+            quiz_id,
+            question_id: question.id,
+            option_id: option.id,
+            correct: option.is_correct,
+            answered_at: Utc::now(),
+        });
     }
 
-    HttpResponse::BadRequest().json(ErrorResponse{ error: "Invalid question or option".to_string() })
+    HttpResponse::Ok().json(answer_response(quiz, question, option, hints_used))
 }
 
 
@@ -213,6 +484,9 @@ pub async fn submit_answer(
     tag = "Management",
     responses(
         (status = 201, description = "Category created", body = Category),
+        (status = 400, description = "Unknown parent", body = ErrorResponse),
+        (status = 409, description = "Name already taken", body = ErrorResponse),
+        (status = 422, description = "Invalid fields", body = ValidationErrorResponse),
         (status = 500, description = "Internal Server Error")
     )
 )]
@@ -220,18 +494,305 @@ pub async fn create_category(
     data: web::Data<AppState>,
     req: web::Json<CreateCategoryRequest>,
 ) -> impl Responder {
+    if let Err(response) = check_valid(&*req) {
+        return response;
+    }
     let mut categories = match data.categories.write() {
         Ok(c) => c,
         Err(_) => return HttpResponse::InternalServerError().body("Lock poisoned"),
     };
 
+    if let Err(response) = check_parent(&categories, None, req.parent_id) {
+        return response;
+    }
+    let name = match check_category_name(&categories, &req.name, req.parent_id, None) {
+        Ok(name) => name,
+        Err(response) => return response,
+    };
     let id = Id::new();
-    let new_category = Category { id, name: req.name.clone() };
+    let new_category = Category { id, name, parent_id: req.parent_id };
     categories.push(new_category.clone());
     
     HttpResponse::Created().json(new_category)
 }
 
+/// Trims a category name and rejects names already used by another category with the same parent.
+fn check_category_name(categories: &[Category], name: &str, parent_id: Option<Id>, except: Option<Id>) -> Result<String, HttpResponse> {
+    let name = name.trim();
+    let taken = categories.iter()
+        .filter(|c| c.parent_id == parent_id)
+        .any(|c| Some(c.id) != except && c.name.to_lowercase() == name.to_lowercase());
+    if taken {
+        return Err(HttpResponse::Conflict().json(ErrorResponse { error: format!("Category '{}' already exists", name) }));
+    }
+    Ok(name.to_string())
+}
+
+/// Rejects a parent that doesn't exist, or that would put category `id` inside itself.
+fn check_parent(categories: &[Category], id: Option<Id>, parent_id: Option<Id>) -> Result<(), HttpResponse> {
+    let Some(parent_id) = parent_id else { return Ok(()) };
+    if !categories.iter().any(|c| c.id == parent_id) {
+        return Err(HttpResponse::BadRequest().json(ErrorResponse { error: format!("Parent category {} does not exist", parent_id) }));
+    }
+    if id.is_some_and(|id| categories::creates_cycle(categories, id, parent_id)) {
+        return Err(HttpResponse::BadRequest().json(ErrorResponse { error: "A category cannot be moved inside itself".to_string() }));
+    }
+    Ok(())
+}
+
+/// Rejects a `category_id` that doesn't name an existing category.
+/// Call while holding the quizzes lock, which is taken before the categories lock.
+fn check_category(data: &AppState, category_id: Option<Id>) -> Result<(), HttpResponse> {
+    let Some(category_id) = category_id else { return Ok(()) };
+    let categories = data.categories.read()
+        .map_err(|_| HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }))?;
+    if categories.iter().any(|c| c.id == category_id) {
+        Ok(())
+    } else {
+        Err(HttpResponse::BadRequest().json(ErrorResponse { error: format!("Category {} does not exist", category_id) }))
+    }
+}
+
+/// Rejects a body, or the model it would produce, that breaks its field rules.
+fn check_valid(value: &impl Validate) -> Result<(), HttpResponse> {
+    validation::validate(value).map_err(|fields| {
+        HttpResponse::UnprocessableEntity().json(ValidationErrorResponse { error: "Validation failed".to_string(), fields })
+    })
+}
+
+/// Builds the 422 for a body or query string that doesn't fit its request type.
+/// Serde names the field when one is missing or unknown; other errors, such as a
+/// wrong type, are reported against `fallback` with serde's message.
+fn deserialize_error(message: String, fallback: &str) -> HttpResponse {
+    let field = ["missing field `", "unknown field `"].iter()
+        .find_map(|prefix| message.strip_prefix(prefix))
+        .and_then(|rest| rest.split('`').next())
+        .unwrap_or(fallback)
+        .to_string();
+    HttpResponse::UnprocessableEntity().json(ValidationErrorResponse {
+        error: "Validation failed".to_string(),
+        fields: vec![FieldError { field, message }],
+    })
+}
+
+/// `JsonConfig` error handler: JSON bodies with missing or mistyped fields get a 422;
+/// bodies that aren't JSON at all keep actix's 400.
+pub fn json_error(err: JsonPayloadError, _req: &HttpRequest) -> actix_web::Error {
+    match err {
+        JsonPayloadError::Deserialize(e) if e.is_data() => {
+            let response = deserialize_error(e.to_string(), "body");
+            InternalError::from_response(e, response).into()
+        }
+        err => err.into(),
+    }
+}
+
+/// `QueryConfig` error handler: query strings with missing or mistyped parameters get a 422.
+pub fn query_error(err: QueryPayloadError, _req: &HttpRequest) -> actix_web::Error {
+    match err {
+        QueryPayloadError::Deserialize(e) => {
+            let response = deserialize_error(e.to_string(), "query");
+            InternalError::from_response(e, response).into()
+        }
+        err => err.into(),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/categories/{id}",
+    tag = "Consumption",
+    params(
+        ("id" = Id, Path, description = "Category ID")
+    ),
+    responses(
+        (status = 200, description = "Get Category by ID", body = Category),
+        (status = 404, description = "Category not found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn get_category(data: web::Data<AppState>, path: web::Path<Id>) -> impl Responder {
+    let categories = match data.categories.read() {
+        Ok(c) => c,
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Lock poisoned".to_string(),
+            });
+        }
+    };
+
+    let id = path.into_inner();
+    match categories.iter().find(|c| c.id == id) {
+        Some(category) => HttpResponse::Ok().json(category),
+        None => HttpResponse::NotFound().json(ErrorResponse {
+            error: "Category not found".to_string(),
+        }),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/categories/tree",
+    tag = "Consumption",
+    responses(
+        (status = 200, description = "All categories nested under their parents", body = Vec<CategoryNode>),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn get_category_tree(data: web::Data<AppState>) -> impl Responder {
+    let categories = match data.categories.read() {
+        Ok(c) => c,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+
+    HttpResponse::Ok().json(categories::tree(&categories))
+}
+
+#[utoipa::path(
+    get,
+    path = "/categories/{id}/path",
+    tag = "Consumption",
+    params(
+        ("id" = Id, Path, description = "Category ID")
+    ),
+    responses(
+        (status = 200, description = "Breadcrumb from the top-level category down to this one", body = Vec<Category>),
+        (status = 404, description = "Category not found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn get_category_path(
+    data: web::Data<AppState>,
+    path: web::Path<Id>,
+) -> impl Responder {
+    let categories = match data.categories.read() {
+        Ok(c) => c,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+
+    let breadcrumb = categories::breadcrumb(&categories, path.into_inner());
+    if breadcrumb.is_empty() {
+        return HttpResponse::NotFound().json(ErrorResponse { error: "Category not found".to_string() });
+    }
+    HttpResponse::Ok().json(breadcrumb)
+}
+
+#[utoipa::path(
+    put,
+    path = "/categories/{id}",
+    request_body = UpdateCategoryRequest,
+    tag = "Management",
+    params(
+        ("id" = Id, Path, description = "Category ID")
+    ),
+    responses(
+        (status = 200, description = "Category renamed or moved", body = Category),
+        (status = 400, description = "Unknown parent or a cycle", body = ErrorResponse),
+        (status = 404, description = "Category not found", body = ErrorResponse),
+        (status = 409, description = "Name already taken", body = ErrorResponse),
+        (status = 422, description = "Invalid fields", body = ValidationErrorResponse),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn update_category(
+    data: web::Data<AppState>,
+    path: web::Path<Id>,
+    req: web::Json<UpdateCategoryRequest>,
+) -> impl Responder {
+    if let Err(response) = check_valid(&*req) {
+        return response;
+    }
+    let mut categories = match data.categories.write() {
+        Ok(c) => c,
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Lock poisoned".to_string(),
+            });
+        }
+    };
+
+    let id = path.into_inner();
+    let Some(index) = categories.iter().position(|c| c.id == id) else {
+        return HttpResponse::NotFound().json(ErrorResponse {
+            error: "Category not found".to_string(),
+        });
+    };
+    let parent_id = req.parent_id.unwrap_or(categories[index].parent_id);
+    if let Err(response) = check_parent(&categories, Some(id), parent_id) {
+        return response;
+    }
+    let name = req.name.as_deref().unwrap_or(&categories[index].name);
+    let name = match check_category_name(&categories, name, parent_id, Some(id)) {
+        Ok(name) => name,
+        Err(response) => return response,
+    };
+    categories[index].name = name;
+    categories[index].parent_id = parent_id;
+    HttpResponse::Ok().json(&categories[index])
+}
+
+#[utoipa::path(
+    delete,
+    path = "/categories/{id}",
+    tag = "Management",
+    params(
+        ("id" = Id, Path, description = "Category ID")
+    ),
+    responses(
+        (status = 204, description = "Category deleted; its quizzes are left without a category and its subcategories move up a level"),
+        (status = 404, description = "Category not found", body = ErrorResponse),
+        (status = 409, description = "A subcategory's name is already used a level up", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn delete_category(data: web::Data<AppState>, path: web::Path<Id>) -> impl Responder {
+    let mut quizzes = match data.quizzes.write() {
+        Ok(q) => q,
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Lock poisoned".to_string(),
+            });
+        }
+    };
+    let mut categories = match data.categories.write() {
+        Ok(c) => c,
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Lock poisoned".to_string(),
+            });
+        }
+    };
+
+    let id = path.into_inner();
+    let Some(index) = categories.iter().position(|c| c.id == id) else {
+        return HttpResponse::NotFound().json(ErrorResponse {
+            error: "Category not found".to_string(),
+        });
+    };
+    // Subcategories move up a level, where their names must stay unique
+    let remaining: Vec<Category> = categories.iter().filter(|c| c.id != id).cloned().collect();
+    for child in remaining.iter().filter(|c| c.parent_id == Some(id)) {
+        if let Err(response) = check_category_name(
+            &remaining,
+            &child.name,
+            categories[index].parent_id,
+            Some(child.id),
+        ) {
+            return response;
+        }
+    }
+    let removed = categories.remove(index);
+    for child in categories.iter_mut().filter(|c| c.parent_id == Some(id)) {
+        child.parent_id = removed.parent_id;
+    }
+
+    // Quizzes outlive their category
+    for quiz in quizzes.iter_mut().filter(|q| q.category_id == Some(id)) {
+        quiz.category_id = None;
+    }
+    HttpResponse::NoContent().finish()
+}
+
 #[utoipa::path(
     get,
     path = "/categories",
@@ -240,11 +801,14 @@ pub async fn create_category(
         PaginationParams
     ),
     responses(
-        (status = 200, description = "List Categories", body = Vec<Category>),
+        (status = 200, description = "List Categories", body = CategoryPage),
+        (status = 400, description = "Invalid paging parameters", body = ErrorResponse),
+        (status = 422, description = "Invalid query parameters", body = ValidationErrorResponse),
         (status = 500, description = "Internal Server Error")
     )
 )]
 pub async fn list_categories(
+    request: HttpRequest,
     data: web::Data<AppState>, 
     filter: web::Query<PaginationParams>,
 ) -> impl Responder {
@@ -253,18 +817,67 @@ pub async fn list_categories(
         Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
     };
 
-    let page = filter.page.unwrap_or(1);
-    let per_page = filter.per_page.unwrap_or(10);
+    let mut listed = categories.clone();
+    listed.sort_by_key(|c| c.id.to_i64());
+    paged_response(paginate(listed, &filter, Some(|c| c.id), &page_links(&request)))
+}
+
+#[derive(serde::Deserialize, utoipa::IntoParams)]
+pub struct TagListParams {
+    /// Only count quizzes in this category
+    pub category_id: Option<Id>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/tags",
+    tag = "Consumption",
+    params(
+        TagListParams
+    ),
+    responses(
+        (status = 200, description = "Tags with usage counts, most used first", body = Vec<TagUsage>),
+        (status = 422, description = "Invalid query parameters", body = ValidationErrorResponse),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn list_tags(
+    data: web::Data<AppState>,
+    params: web::Query<TagListParams>,
+) -> impl Responder {
+    let quizzes = match data.quizzes.read() {
+        Ok(q) => q,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+
+    let registry = match data.tags.read() {
+        Ok(t) => t,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
 
-    let total = categories.len();
-    let start = ((page - 1) * per_page) as usize;
-    if start >= total {
-        return HttpResponse::Ok().json(Vec::<Category>::new());
+    let mut usage: HashMap<&str, TagUsage> = HashMap::new();
+    for quiz in quizzes.iter().filter(|q| !q.generated) {
+        if params.category_id.is_some() && quiz.category_id != params.category_id {
+            continue;
+        }
+        let mut seen = HashSet::new();
+        for tag in quiz.tags.iter().filter(|t| seen.insert(t.as_str())) {
+            // Tags are registered whenever a quiz is written
+            let Some(registered) = registry.iter().find(|t| t.name == *tag) else { continue };
+            let entry = usage.entry(tag).or_insert_with(|| TagUsage {
+                id: registered.id,
+                name: tag.clone(),
+                quiz_count: 0,
+                question_count: 0,
+            });
+            entry.quiz_count += 1;
+            entry.question_count += quiz.questions.len();
+        }
     }
-    let end = std::cmp::min(start + per_page as usize, total);
 
-    let page_items: Vec<Category> = categories.iter().skip(start).take(per_page as usize).cloned().collect();
-    HttpResponse::Ok().json(page_items)
+    let mut tags: Vec<TagUsage> = usage.into_values().collect();
+    tags.sort_by(|a, b| b.quiz_count.cmp(&a.quiz_count).then_with(|| a.name.cmp(&b.name)));
+    HttpResponse::Ok().json(tags)
 }
 
 #[utoipa::path(
@@ -294,6 +907,8 @@ pub async fn delete_quiz(
     quizzes.retain(|q| q.id != id);
     
     if quizzes.len() < initial_len {
+        data.reindex_questions(&quizzes);
+        data.prune_tags(&quizzes);
         HttpResponse::NoContent().finish()
     } else {
         HttpResponse::NotFound().body("Quiz not found")
@@ -310,7 +925,9 @@ pub async fn delete_quiz(
     ),
     responses(
         (status = 200, description = "Quiz updated", body = Quiz),
+        (status = 400, description = "Unknown category", body = ErrorResponse),
         (status = 404, description = "Quiz not found"),
+        (status = 422, description = "Invalid fields", body = ValidationErrorResponse),
         (status = 500, description = "Internal Server Error")
     )
 )]
@@ -319,11 +936,17 @@ pub async fn update_quiz(
     path: web::Path<Id>,
     req: web::Json<UpdateQuizRequest>,
 ) -> impl Responder {
+    if let Err(response) = check_valid(&*req) {
+        return response;
+    }
     let mut quizzes = match data.quizzes.write() {
         Ok(q) => q,
         Err(_) => return HttpResponse::InternalServerError().body("Database error"),
     };
-    
+    if let Err(response) = check_category(&data, req.category_id) {
+        return response;
+    }
+
     let id = path.into_inner();
     if let Some(quiz) = quizzes.iter_mut().find(|q| q.id == id) {
         if let Some(title) = &req.title {
@@ -332,31 +955,553 @@ pub async fn update_quiz(
         if let Some(cat_id) = req.category_id {
             quiz.category_id = Some(cat_id);
         }
-        if let Some(tags) = &req.tags {
-            quiz.tags = tags.clone();
+        if let Some(names) = &req.tags {
+            quiz.tags = tags::normalize_all(names);
+            data.register_tags(&quiz.tags);
         }
-         HttpResponse::Ok().json(quiz.clone())
+        if let Some(difficulty) = req.difficulty {
+            quiz.difficulty = Some(difficulty);
+        }
+        if let Some(policy) = req.reveal_policy {
+            quiz.reveal_policy = policy;
+        }
+        if let Some(limit) = req.time_limit_seconds {
+            quiz.time_limit_seconds = Some(limit);
+        }
+        if let Some(exam_mode) = req.exam_mode {
+            quiz.exam_mode = exam_mode;
+        }
+        if let Some(shuffle) = req.shuffle_options {
+            quiz.shuffle_options = shuffle;
+        }
+        if let Some(shuffle) = req.shuffle_questions {
+            quiz.shuffle_questions = shuffle;
+        }
+        let updated = quiz.clone();
+        if req.tags.is_some() {
+            data.prune_tags(&quizzes);
+        }
+        data.invalidate_search();
+        HttpResponse::Ok().json(updated)
     } else {
-         HttpResponse::NotFound().body("Quiz not found")
+        HttpResponse::NotFound().body("Quiz not found")
     }
 }
 
-#[derive(serde::Deserialize, utoipa::IntoParams)]
-pub struct RandomQuizParams {
-    pub tag: Option<String>,
-    pub user_email: Option<String>, // Ignored in memory for history tracking
-}
-
 #[utoipa::path(
-    get,
-    path = "/quizzes/random",
-    tag = "Consumption",
+    patch,
+    path = "/quizzes/{id}",
+    request_body(
+        content = Object,
+        content_type = "application/merge-patch+json",
+        description = "RFC 7396 merge patch, or an RFC 6902 JSON Patch sent as `application/json-patch+json`. \
+            Patches apply to the quiz JSON including `is_correct` on options and `hints` on questions; \
+            questions and options added without an `id` get a new one."
+    ),
+    tag = "Management",
+    params(
+        ("id" = Id, Path, description = "Quiz ID")
+    ),
+    responses(
+        (status = 200, description = "Quiz patched", body = Quiz),
+        (status = 400, description = "Malformed patch", body = ErrorResponse),
+        (status = 403, description = "Generated quizzes cannot be edited", body = ErrorResponse),
+        (status = 404, description = "Quiz not found", body = ErrorResponse),
+        (status = 409, description = "A JSON Patch test operation failed", body = ErrorResponse),
+        (status = 415, description = "Unsupported patch format", body = ErrorResponse),
+        (status = 422, description = "The patch does not apply or the patched quiz is invalid; \
+            broken field rules come as a ValidationErrorResponse listing the fields", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn patch_quiz(
+    request: HttpRequest,
+    data: web::Data<AppState>,
+    path: web::Path<Id>,
+    body: web::Bytes,
+) -> impl Responder {
+    let content_type = request.headers().get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .map(|value| value.trim().to_ascii_lowercase())
+        .unwrap_or_default();
+    if content_type != patch::MERGE_PATCH && content_type != patch::JSON_PATCH {
+        return HttpResponse::UnsupportedMediaType().json(ErrorResponse {
+            error: format!("Use {} or {}", patch::MERGE_PATCH, patch::JSON_PATCH),
+        });
+    }
+    let patch_document: serde_json::Value = match serde_json::from_slice(&body) {
+        Ok(document) => document,
+        Err(e) => return HttpResponse::BadRequest().json(ErrorResponse { error: format!("Invalid JSON: {}", e) }),
+    };
+
+    let mut quizzes = match data.quizzes.write() {
+        Ok(q) => q,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+    let id = path.into_inner();
+    let index = match editable_position(&quizzes, id) {
+        Ok(index) => index,
+        Err(response) => return response,
+    };
+
+    let mut document = patch::quiz_document(&quizzes[index]);
+    if content_type == patch::MERGE_PATCH {
+        patch::merge_patch(&mut document, &patch_document);
+    } else if let Err(e) = patch::json_patch(&mut document, &patch_document) {
+        return match e {
+            PatchError::Invalid(error) => HttpResponse::BadRequest().json(ErrorResponse { error }),
+            PatchError::TestFailed(error) => HttpResponse::Conflict().json(ErrorResponse { error }),
+            PatchError::Unprocessable(error) => HttpResponse::UnprocessableEntity().json(ErrorResponse { error }),
+        };
+    }
+
+    // Nothing is stored unless the whole patched quiz is valid
+    let mut patched = match patch::quiz_from_document(document, &quizzes[index]) {
+        Ok(quiz) => quiz,
+        Err(error) => return HttpResponse::UnprocessableEntity().json(ErrorResponse { error }),
+    };
+    if let Err(error) = patch::validate(&quizzes, &quizzes[index], &patched) {
+        return HttpResponse::UnprocessableEntity().json(ErrorResponse { error });
+    }
+    if let Err(response) = check_valid(&patched) {
+        return response;
+    }
+    if check_category(&data, patched.category_id).is_err() {
+        return HttpResponse::UnprocessableEntity().json(ErrorResponse { error: "category_id does not name an existing category".to_string() });
+    }
+    patched.tags = tags::normalize_all(&patched.tags);
+    data.register_tags(&patched.tags);
+
+    let same_questions = quizzes[index].questions.len() == patched.questions.len()
+        && patched.questions.iter().all(|q| quizzes[index].questions.iter().any(|old| old.id == q.id));
+    quizzes[index] = patched.clone();
+    data.prune_tags(&quizzes);
+    if same_questions {
+        data.invalidate_search();
+    } else {
+        data.reindex_questions(&quizzes);
+    }
+    HttpResponse::Ok().json(patched)
+}
+
+/// Finds a quiz whose questions may be edited. Generated quizzes hold copies of
+/// questions from other quizzes and stay as they were drawn.
+fn editable_quiz(quizzes: &mut [Quiz], id: Id) -> Result<&mut Quiz, HttpResponse> {
+    editable_position(quizzes, id).map(|index| &mut quizzes[index])
+}
+
+/// Like [`editable_quiz`], but returns the quiz's position in `quizzes`.
+fn editable_position(quizzes: &[Quiz], id: Id) -> Result<usize, HttpResponse> {
+    match quizzes.iter().position(|q| q.id == id) {
+        Some(index) if quizzes[index].generated => Err(HttpResponse::Forbidden().json(ErrorResponse { error: "Generated quizzes cannot be edited".to_string() })),
+        Some(index) => Ok(index),
+        None => Err(HttpResponse::NotFound().json(ErrorResponse { error: "Quiz not found".to_string() })),
+    }
+}
+
+fn editable_question(quiz: &mut Quiz, id: Id) -> Result<&mut Question, HttpResponse> {
+    quiz.questions.iter_mut()
+        .find(|q| q.id == id)
+        .ok_or_else(|| HttpResponse::NotFound().json(ErrorResponse { error: "Question not found".to_string() }))
+}
+
+/// Puts `items` in the order of `ids`, which must name every item exactly once.
+fn reorder<T>(items: &mut [T], ids: &[Id], id_of: fn(&T) -> Id) -> Result<(), HttpResponse> {
+    let unique: HashSet<Id> = ids.iter().copied().collect();
+    if ids.len() != items.len() || unique.len() != ids.len() || !items.iter().all(|item| unique.contains(&id_of(item))) {
+        return Err(HttpResponse::BadRequest().json(ErrorResponse { error: "ids must list every existing ID exactly once".to_string() }));
+    }
+    items.sort_by_key(|item| ids.iter().position(|id| *id == id_of(item)));
+    Ok(())
+}
+
+#[utoipa::path(
+    post,
+    path = "/quizzes/{id}/questions",
+    request_body = CreateQuestionRequest,
+    tag = "Management",
+    params(
+        ("id" = Id, Path, description = "Quiz ID")
+    ),
+    responses(
+        (status = 201, description = "Question added at the end of the quiz", body = Question),
+        (status = 403, description = "Generated quizzes cannot be edited", body = ErrorResponse),
+        (status = 404, description = "Quiz not found", body = ErrorResponse),
+        (status = 422, description = "Invalid fields", body = ValidationErrorResponse),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn add_question(
+    data: web::Data<AppState>,
+    path: web::Path<Id>,
+    req: web::Json<CreateQuestionRequest>,
+) -> impl Responder {
+    if let Err(response) = check_valid(&*req) {
+        return response;
+    }
+    let mut quizzes = match data.quizzes.write() {
+        Ok(q) => q,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+
+    let quiz = match editable_quiz(&mut quizzes, path.into_inner()) {
+        Ok(quiz) => quiz,
+        Err(response) => return response,
+    };
+    let question = Question::from(&*req);
+    quiz.questions.push(question.clone());
+    data.reindex_questions(&quizzes);
+    HttpResponse::Created().json(question)
+}
+
+#[utoipa::path(
+    put,
+    path = "/quizzes/{id}/questions/order",
+    request_body = ReorderRequest,
+    tag = "Management",
+    params(
+        ("id" = Id, Path, description = "Quiz ID")
+    ),
+    responses(
+        (status = 200, description = "Questions reordered", body = Quiz),
+        (status = 400, description = "ids is not a permutation of the question IDs", body = ErrorResponse),
+        (status = 403, description = "Generated quizzes cannot be edited", body = ErrorResponse),
+        (status = 404, description = "Quiz not found", body = ErrorResponse),
+        (status = 422, description = "ids is empty or repeats an ID", body = ValidationErrorResponse),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn reorder_questions(
+    data: web::Data<AppState>,
+    path: web::Path<Id>,
+    req: web::Json<ReorderRequest>,
+) -> impl Responder {
+    if let Err(response) = check_valid(&*req) {
+        return response;
+    }
+    let mut quizzes = match data.quizzes.write() {
+        Ok(q) => q,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+
+    let quiz = match editable_quiz(&mut quizzes, path.into_inner()) {
+        Ok(quiz) => quiz,
+        Err(response) => return response,
+    };
+    if let Err(response) = reorder(&mut quiz.questions, &req.ids, |q| q.id) {
+        return response;
+    }
+    HttpResponse::Ok().json(&*quiz)
+}
+
+#[utoipa::path(
+    put,
+    path = "/quizzes/{id}/questions/{question_id}",
+    request_body = UpdateQuestionRequest,
+    tag = "Management",
+    params(
+        ("id" = Id, Path, description = "Quiz ID"),
+        ("question_id" = Id, Path, description = "Question ID")
+    ),
+    responses(
+        (status = 200, description = "Question updated; its options keep their IDs", body = Question),
+        (status = 403, description = "Generated quizzes cannot be edited", body = ErrorResponse),
+        (status = 404, description = "Quiz or question not found", body = ErrorResponse),
+        (status = 422, description = "Invalid fields", body = ValidationErrorResponse),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn update_question(
+    data: web::Data<AppState>,
+    path: web::Path<(Id, Id)>,
+    req: web::Json<UpdateQuestionRequest>,
+) -> impl Responder {
+    if let Err(response) = check_valid(&*req) {
+        return response;
+    }
+    let (quiz_id, question_id) = path.into_inner();
+    let mut quizzes = match data.quizzes.write() {
+        Ok(q) => q,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+
+    let question = match editable_quiz(&mut quizzes, quiz_id).and_then(|quiz| editable_question(quiz, question_id)) {
+        Ok(question) => question,
+        Err(response) => return response,
+    };
+    if let Some(text) = &req.text {
+        question.text = text.clone();
+    }
+    if let Some(explanation) = &req.explanation {
+        question.explanation = Some(explanation.clone());
+    }
+    if let Some(difficulty) = req.difficulty {
+        question.difficulty = Some(difficulty);
+    }
+    if let Some(seconds) = req.estimated_time_seconds {
+        question.estimated_time_seconds = Some(seconds);
+    }
+    if let Some(limit) = req.time_limit_seconds {
+        question.time_limit_seconds = Some(limit);
+    }
+    if let Some(hints) = &req.hints {
+        question.hints = hints.clone();
+    }
+    let updated = question.clone();
+    data.invalidate_search();
+    HttpResponse::Ok().json(updated)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/quizzes/{id}/questions/{question_id}",
+    tag = "Management",
+    params(
+        ("id" = Id, Path, description = "Quiz ID"),
+        ("question_id" = Id, Path, description = "Question ID")
+    ),
+    responses(
+        (status = 204, description = "Question deleted"),
+        (status = 403, description = "Generated quizzes cannot be edited", body = ErrorResponse),
+        (status = 404, description = "Quiz or question not found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn delete_question(
+    data: web::Data<AppState>,
+    path: web::Path<(Id, Id)>,
+) -> impl Responder {
+    let (quiz_id, question_id) = path.into_inner();
+    let mut quizzes = match data.quizzes.write() {
+        Ok(q) => q,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+
+    let quiz = match editable_quiz(&mut quizzes, quiz_id) {
+        Ok(quiz) => quiz,
+        Err(response) => return response,
+    };
+    let initial_len = quiz.questions.len();
+    quiz.questions.retain(|q| q.id != question_id);
+    if quiz.questions.len() == initial_len {
+        return HttpResponse::NotFound().json(ErrorResponse { error: "Question not found".to_string() });
+    }
+    data.reindex_questions(&quizzes);
+    HttpResponse::NoContent().finish()
+}
+
+#[utoipa::path(
+    post,
+    path = "/quizzes/{id}/questions/{question_id}/options",
+    request_body = CreateOptionRequest,
+    tag = "Management",
+    params(
+        ("id" = Id, Path, description = "Quiz ID"),
+        ("question_id" = Id, Path, description = "Question ID")
+    ),
+    responses(
+        (status = 201, description = "Option added at the end of the question", body = QuestionOption),
+        (status = 403, description = "Generated quizzes cannot be edited", body = ErrorResponse),
+        (status = 404, description = "Quiz or question not found", body = ErrorResponse),
+        (status = 422, description = "Invalid fields", body = ValidationErrorResponse),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn add_option(
+    data: web::Data<AppState>,
+    path: web::Path<(Id, Id)>,
+    req: web::Json<CreateOptionRequest>,
+) -> impl Responder {
+    if let Err(response) = check_valid(&*req) {
+        return response;
+    }
+    let (quiz_id, question_id) = path.into_inner();
+    let mut quizzes = match data.quizzes.write() {
+        Ok(q) => q,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+
+    let question = match editable_quiz(&mut quizzes, quiz_id).and_then(|quiz| editable_question(quiz, question_id)) {
+        Ok(question) => question,
+        Err(response) => return response,
+    };
+    let option = QuestionOption::from(&*req);
+    question.options.push(option.clone());
+    data.invalidate_search();
+    HttpResponse::Created().json(option)
+}
+
+#[utoipa::path(
+    put,
+    path = "/quizzes/{id}/questions/{question_id}/options/order",
+    request_body = ReorderRequest,
+    tag = "Management",
+    params(
+        ("id" = Id, Path, description = "Quiz ID"),
+        ("question_id" = Id, Path, description = "Question ID")
+    ),
+    responses(
+        (status = 200, description = "Options reordered", body = Question),
+        (status = 400, description = "ids is not a permutation of the option IDs", body = ErrorResponse),
+        (status = 403, description = "Generated quizzes cannot be edited", body = ErrorResponse),
+        (status = 404, description = "Quiz or question not found", body = ErrorResponse),
+        (status = 422, description = "ids is empty or repeats an ID", body = ValidationErrorResponse),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn reorder_options(
+    data: web::Data<AppState>,
+    path: web::Path<(Id, Id)>,
+    req: web::Json<ReorderRequest>,
+) -> impl Responder {
+    if let Err(response) = check_valid(&*req) {
+        return response;
+    }
+    let (quiz_id, question_id) = path.into_inner();
+    let mut quizzes = match data.quizzes.write() {
+        Ok(q) => q,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+
+    let question = match editable_quiz(&mut quizzes, quiz_id).and_then(|quiz| editable_question(quiz, question_id)) {
+        Ok(question) => question,
+        Err(response) => return response,
+    };
+    if let Err(response) = reorder(&mut question.options, &req.ids, |o| o.id) {
+        return response;
+    }
+    HttpResponse::Ok().json(&*question)
+}
+
+#[utoipa::path(
+    put,
+    path = "/quizzes/{id}/questions/{question_id}/options/{option_id}",
+    request_body = UpdateOptionRequest,
+    tag = "Management",
+    params(
+        ("id" = Id, Path, description = "Quiz ID"),
+        ("question_id" = Id, Path, description = "Question ID"),
+        ("option_id" = Id, Path, description = "Option ID")
+    ),
+    responses(
+        (status = 200, description = "Option updated", body = QuestionOption),
+        (status = 403, description = "Generated quizzes cannot be edited", body = ErrorResponse),
+        (status = 404, description = "Quiz, question or option not found", body = ErrorResponse),
+        (status = 422, description = "Invalid fields, or the question would have no correct option", body = ValidationErrorResponse),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn update_option(
+    data: web::Data<AppState>,
+    path: web::Path<(Id, Id, Id)>,
+    req: web::Json<UpdateOptionRequest>,
+) -> impl Responder {
+    if let Err(response) = check_valid(&*req) {
+        return response;
+    }
+    let (quiz_id, question_id, option_id) = path.into_inner();
+    let mut quizzes = match data.quizzes.write() {
+        Ok(q) => q,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+
+    let question = match editable_quiz(&mut quizzes, quiz_id).and_then(|quiz| editable_question(quiz, question_id)) {
+        Ok(question) => question,
+        Err(response) => return response,
+    };
+    let mut edited = question.clone();
+    let Some(option) = edited.options.iter_mut().find(|o| o.id == option_id) else {
+        return HttpResponse::NotFound().json(ErrorResponse { error: "Option not found".to_string() });
+    };
+    if let Some(text) = &req.text {
+        option.text = text.clone();
+    }
+    if let Some(is_correct) = req.is_correct {
+        option.is_correct = is_correct;
+    }
+    if let Some(description) = &req.description {
+        option.description = Some(description.clone());
+    }
+    let updated = option.clone();
+    if let Err(response) = check_valid(&edited) {
+        return response;
+    }
+    *question = edited;
+    data.invalidate_search();
+    HttpResponse::Ok().json(updated)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/quizzes/{id}/questions/{question_id}/options/{option_id}",
+    tag = "Management",
+    params(
+        ("id" = Id, Path, description = "Quiz ID"),
+        ("question_id" = Id, Path, description = "Question ID"),
+        ("option_id" = Id, Path, description = "Option ID")
+    ),
+    responses(
+        (status = 204, description = "Option deleted"),
+        (status = 403, description = "Generated quizzes cannot be edited", body = ErrorResponse),
+        (status = 404, description = "Quiz, question or option not found", body = ErrorResponse),
+        (status = 422, description = "The question would have no option or no correct option left", body = ValidationErrorResponse),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn delete_option(
+    data: web::Data<AppState>,
+    path: web::Path<(Id, Id, Id)>,
+) -> impl Responder {
+    let (quiz_id, question_id, option_id) = path.into_inner();
+    let mut quizzes = match data.quizzes.write() {
+        Ok(q) => q,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+
+    let question = match editable_quiz(&mut quizzes, quiz_id).and_then(|quiz| editable_question(quiz, question_id)) {
+        Ok(question) => question,
+        Err(response) => return response,
+    };
+    let mut edited = question.clone();
+    edited.options.retain(|o| o.id != option_id);
+    if edited.options.len() == question.options.len() {
+        return HttpResponse::NotFound().json(ErrorResponse { error: "Option not found".to_string() });
+    }
+    if let Err(response) = check_valid(&edited) {
+        return response;
+    }
+    *question = edited;
+    data.invalidate_search();
+    HttpResponse::NoContent().finish()
+}
+
+#[derive(serde::Deserialize, utoipa::IntoParams)]
+pub struct RandomQuizParams {
+    pub tag: Option<String>,
+    #[param(inline)]
+    pub difficulty: Option<Difficulty>,
+    /// Only quizzes whose total estimated time (seconds) fits within this limit
+    pub max_estimated_time: Option<u32>,
+    /// Comma-separated quiz IDs to leave out
+    pub exclude_ids: Option<String>,
+    /// Prefer quizzes with questions this user hasn't seen or got wrong
+    pub user_email: Option<String>,
+    /// Makes the pick reproducible for the same inputs
+    pub seed: Option<u64>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/quizzes/random",
+    tag = "Consumption",
     params(
         RandomQuizParams
     ),
     responses(
         (status = 200, description = "Random Quiz", body = Quiz),
         (status = 404, description = "No quizzes found"),
+        (status = 422, description = "Invalid query parameters", body = ValidationErrorResponse),
         (status = 500, description = "Internal Server Error")
     )
 )]
@@ -369,20 +1514,1295 @@ pub async fn get_random_quiz(
         Err(_) => return HttpResponse::InternalServerError().body("Lock poisoned"),
     };
 
+    let exclude_ids = parse_id_list(params.exclude_ids.as_deref());
+    let tag = params.tag.as_deref().and_then(tags::normalize);
     let filtered: Vec<&Quiz> = quizzes.iter().filter(|q| {
-        if let Some(tag) = &params.tag {
-            return q.tags.contains(tag);
-        }
-        true
+        if q.generated || exclude_ids.contains(&q.id) { return false; }
+        if let Some(tag) = &tag
+            && !q.tags.contains(tag) { return false; }
+        if params.difficulty.is_some() && q.difficulty != params.difficulty { return false; }
+        matches_time_limit(q, params.max_estimated_time)
     }).collect();
 
-    if filtered.is_empty() {
-        return HttpResponse::NotFound().body("No quizzes found");
+    let history = match data.history.read() {
+        Ok(h) => h,
+        Err(_) => return HttpResponse::InternalServerError().body("Lock poisoned"),
+    };
+    let records = params.user_email.as_deref()
+        .and_then(|email| history.get(&learner_key(email)))
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+
+    let mut rng = seeded_rng(params.seed);
+    match pick_quiz(&filtered, records, &mut rng) {
+        Some(quiz) => HttpResponse::Ok().json(learner_view(quiz)),
+        None => HttpResponse::NotFound().body("No quizzes found"),
     }
+}
+
+#[derive(serde::Deserialize, utoipa::IntoParams)]
+pub struct HintParams {
+    /// Attempt the hint is revealed in; the hint penalty applies to that attempt's score
+    pub attempt_id: Option<Id>,
+    /// Learner the hint is revealed to, when answering outside an attempt
+    pub user_email: Option<String>,
+}
 
-    let mut rng = rand::thread_rng();
-    let random_index = rng.gen_range(0..filtered.len());
-    let quiz = filtered[random_index];
+/// Reveals the hint after the `revealed` ones already shown, if any are left.
+fn reveal_next_hint(hints: &[String], revealed: &mut usize) -> Option<HintResponse> {
+    let hint = hints.get(*revealed)?;
+    *revealed += 1;
+    Some(HintResponse {
+        hint: hint.clone(),
+        hint_number: *revealed,
+        remaining: hints.len() - *revealed,
+        penalty: 1.0 - question_score(true, *revealed),
+    })
+}
+
+#[utoipa::path(
+    get,
+    path = "/quizzes/{id}/questions/{question_id}/hints/next",
+    tag = "Consumption",
+    params(
+        ("id" = Id, Path, description = "Quiz ID"),
+        ("question_id" = Id, Path, description = "Question ID"),
+        HintParams
+    ),
+    responses(
+        (status = 200, description = "Next hint", body = HintResponse),
+        (status = 400, description = "Neither attempt_id nor user_email given", body = ErrorResponse),
+        (status = 404, description = "Quiz, question or attempt not found, or no hints left", body = ErrorResponse),
+        (status = 409, description = "Attempt already finished", body = ErrorResponse),
+        (status = 422, description = "Invalid query parameters", body = ValidationErrorResponse),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn next_hint(
+    data: web::Data<AppState>,
+    path: web::Path<(Id, Id)>,
+    params: web::Query<HintParams>,
+) -> impl Responder {
+    let quizzes = match data.quizzes.read() {
+        Ok(q) => q,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+
+    let (quiz_id, question_id) = path.into_inner();
+    let Some(quiz) = quizzes.iter().find(|q| q.id == quiz_id) else {
+        return HttpResponse::NotFound().json(ErrorResponse { error: "Quiz not found".to_string() });
+    };
+    let Some(question) = quiz.questions.iter().find(|q| q.id == question_id) else {
+        return HttpResponse::NotFound().json(ErrorResponse { error: "Question not found".to_string() });
+    };
+
+    let hint = if let Some(attempt_id) = params.attempt_id {
+        let mut attempts = match data.attempts.write() {
+            Ok(a) => a,
+            Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+        };
+        let Some(attempt) = attempts.get_mut(&attempt_id).filter(|a| a.quiz_id == quiz_id) else {
+            return HttpResponse::NotFound().json(ErrorResponse { error: "Attempt not found".to_string() });
+        };
+        let expired = attempt.expire_if_overdue(Utc::now());
+        if attempt.is_finished() {
+            if expired {
+                data.settle_attempt(&quizzes, &attempts, attempt_id);
+            }
+            return HttpResponse::Conflict().json(ErrorResponse { error: "Attempt already finished".to_string() });
+        }
+        reveal_next_hint(&question.hints, attempt.hints_used.entry(question_id).or_insert(0))
+    } else if let Some(email) = &params.user_email {
+        let mut usage = match data.hint_usage.write() {
+            Ok(u) => u,
+            Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+        };
+        reveal_next_hint(&question.hints, usage.entry((learner_key(email), question_id)).or_insert(0))
+    } else {
+        return HttpResponse::BadRequest().json(ErrorResponse { error: "attempt_id or user_email is required".to_string() });
+    };
+
+    match hint {
+        Some(hint) => HttpResponse::Ok().json(hint),
+        None => HttpResponse::NotFound().json(ErrorResponse { error: "No more hints".to_string() }),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/quizzes/{id}/attempts",
+    request_body = StartAttemptRequest,
+    tag = "Consumption",
+    params(
+        ("id" = Id, Path, description = "Quiz ID")
+    ),
+    responses(
+        (status = 201, description = "Attempt started", body = Attempt),
+        (status = 403, description = "Quiz is a daily challenge", body = ErrorResponse),
+        (status = 404, description = "Quiz not found", body = ErrorResponse),
+        (status = 422, description = "Invalid fields", body = ValidationErrorResponse),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn start_attempt(
+    data: web::Data<AppState>,
+    path: web::Path<Id>,
+    req: Option<web::Json<StartAttemptRequest>>,
+) -> impl Responder {
+    if let Some(req) = &req
+        && let Err(response) = check_valid(&**req)
+    {
+        return response;
+    }
+    let quizzes = match data.quizzes.read() {
+        Ok(q) => q,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+
+    let quiz_id = path.into_inner();
+    let Some(quiz) = quizzes.iter().find(|q| q.id == quiz_id) else {
+        return HttpResponse::NotFound().json(ErrorResponse { error: "Quiz not found".to_string() });
+    };
+    // Daily challenges allow one attempt per learner, which only /daily/attempts enforces
+    match data.daily_quizzes.read() {
+        Ok(daily) if daily.values().any(|id| *id == quiz_id) => {
+            return HttpResponse::Forbidden().json(ErrorResponse { error: "Daily challenges are started via /daily/attempts".to_string() });
+        }
+        Ok(_) => {}
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    }
+
+    let req = req.map(|r| r.into_inner()).unwrap_or_default();
+    let attempt = Attempt::new(quiz, req.user_email);
+
+    let mut attempts = match data.attempts.write() {
+        Ok(a) => a,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+    attempts.insert(attempt.id, attempt.clone());
+
+    HttpResponse::Created().json(attempt)
+}
+
+#[utoipa::path(
+    get,
+    path = "/quizzes/{id}/attempts/{attempt_id}",
+    tag = "Consumption",
+    params(
+        ("id" = Id, Path, description = "Quiz ID"),
+        ("attempt_id" = Id, Path, description = "Attempt ID")
+    ),
+    responses(
+        (status = 200, description = "Attempt state", body = Attempt),
+        (status = 404, description = "Attempt not found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn get_attempt(
+    data: web::Data<AppState>,
+    path: web::Path<(Id, Id)>,
+) -> impl Responder {
+    let quizzes = match data.quizzes.read() {
+        Ok(q) => q,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+    let mut attempts = match data.attempts.write() {
+        Ok(a) => a,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+
+    let (quiz_id, attempt_id) = path.into_inner();
+    let Some(attempt) = attempts.get_mut(&attempt_id).filter(|a| a.quiz_id == quiz_id) else {
+        return HttpResponse::NotFound().json(ErrorResponse { error: "Attempt not found".to_string() });
+    };
+    if attempt.expire_if_overdue(Utc::now()) {
+        data.settle_attempt(&quizzes, &attempts, attempt_id);
+    }
+    HttpResponse::Ok().json(&attempts[&attempt_id])
+}
+
+#[utoipa::path(
+    get,
+    path = "/quizzes/{id}/attempts/{attempt_id}/questions",
+    tag = "Consumption",
+    params(
+        ("id" = Id, Path, description = "Quiz ID"),
+        ("attempt_id" = Id, Path, description = "Attempt ID")
+    ),
+    responses(
+        (status = 200, description = "Questions in the order of this attempt, with options shuffled unless the quiz disables it", body = [Question]),
+        (status = 404, description = "Quiz or attempt not found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn get_attempt_questions(
+    data: web::Data<AppState>,
+    path: web::Path<(Id, Id)>,
+) -> impl Responder {
+    let quizzes = match data.quizzes.read() {
+        Ok(q) => q,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+    let attempts = match data.attempts.read() {
+        Ok(a) => a,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+
+    let (quiz_id, attempt_id) = path.into_inner();
+    let Some(quiz) = quizzes.iter().find(|q| q.id == quiz_id) else {
+        return HttpResponse::NotFound().json(ErrorResponse { error: "Quiz not found".to_string() });
+    };
+    let Some(attempt) = attempts.get(&attempt_id).filter(|a| a.quiz_id == quiz_id) else {
+        return HttpResponse::NotFound().json(ErrorResponse { error: "Attempt not found".to_string() });
+    };
+
+    let mut questions = attempt_questions(quiz, attempt_id);
+    if quiz.exam_mode && !attempt.is_finished() {
+        withhold_explanations(&mut questions);
+    }
+    HttpResponse::Ok().json(questions)
+}
+
+#[utoipa::path(
+    post,
+    path = "/quizzes/{id}/attempts/{attempt_id}/answers",
+    request_body = AttemptAnswerRequest,
+    tag = "Consumption",
+    params(
+        ("id" = Id, Path, description = "Quiz ID"),
+        ("attempt_id" = Id, Path, description = "Attempt ID")
+    ),
+    responses(
+        (status = 200, description = "Answer recorded", body = AnswerResponse),
+        (status = 404, description = "Quiz, attempt, question or option not found", body = ErrorResponse),
+        (status = 409, description = "Attempt finished or expired, question already answered, or question time limit exceeded", body = ErrorResponse),
+        (status = 422, description = "Invalid fields", body = ValidationErrorResponse),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn submit_attempt_answer(
+    data: web::Data<AppState>,
+    path: web::Path<(Id, Id)>,
+    req: web::Json<AttemptAnswerRequest>,
+) -> impl Responder {
+    if let Err(response) = check_valid(&*req) {
+        return response;
+    }
+    let quizzes = match data.quizzes.read() {
+        Ok(q) => q,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+    let mut attempts = match data.attempts.write() {
+        Ok(a) => a,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+
+    let (quiz_id, attempt_id) = path.into_inner();
+    let Some(quiz) = quizzes.iter().find(|q| q.id == quiz_id) else {
+        return HttpResponse::NotFound().json(ErrorResponse { error: "Quiz not found".to_string() });
+    };
+    let Some(attempt) = attempts.get_mut(&attempt_id).filter(|a| a.quiz_id == quiz_id) else {
+        return HttpResponse::NotFound().json(ErrorResponse { error: "Attempt not found".to_string() });
+    };
+    let now = Utc::now();
+    if attempt.expire_if_overdue(now) {
+        data.settle_attempt(&quizzes, &attempts, attempt_id);
+        return HttpResponse::Conflict().json(ErrorResponse { error: "Attempt time limit exceeded".to_string() });
+    }
+    if attempt.is_finished() {
+        return HttpResponse::Conflict().json(ErrorResponse { error: "Attempt already finished".to_string() });
+    }
+    let Some(question) = quiz.questions.iter().find(|q| q.id == req.question_id) else {
+        return HttpResponse::NotFound().json(ErrorResponse { error: "Question not found".to_string() });
+    };
+    let Some(option) = question.options.iter().find(|o| o.id == req.option_id) else {
+        return HttpResponse::NotFound().json(ErrorResponse { error: "Option not found".to_string() });
+    };
+    if attempt.answer_for(question.id).is_some() {
+        return HttpResponse::Conflict().json(ErrorResponse { error: "Question already answered".to_string() });
+    }
+
+    let timed_out = question.time_limit_seconds
+        .is_some_and(|limit| now - attempt.question_started_at() > Duration::seconds(limit.into()));
+    let answer = AttemptAnswer {
+        question_id: question.id,
+        option_id: option.id,
+        correct: option.is_correct && !timed_out,
+        timed_out,
+        answered_at: now,
+    };
+    // Progress, reviews and ratings would show whether an exam answer was right,
+    // so exam attempts record them on finishing
+    if !quiz.exam_mode {
+        data.record_attempt_answer(&quizzes, quiz, attempt.user_email.as_deref(), &answer);
+    }
+    attempt.answers.push(answer);
+    if timed_out {
+        return HttpResponse::Conflict().json(ErrorResponse { error: "Question time limit exceeded".to_string() });
+    }
+
+    HttpResponse::Ok().json(answer_response(quiz, question, option, attempt.hints_for(question.id)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/quizzes/{id}/attempts/{attempt_id}/finish",
+    tag = "Consumption",
+    params(
+        ("id" = Id, Path, description = "Quiz ID"),
+        ("attempt_id" = Id, Path, description = "Attempt ID")
+    ),
+    responses(
+        (status = 200, description = "Final score and per-question breakdown", body = AttemptResult),
+        (status = 404, description = "Quiz or attempt not found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn finish_attempt(
+    data: web::Data<AppState>,
+    path: web::Path<(Id, Id)>,
+) -> impl Responder {
+    let quizzes = match data.quizzes.read() {
+        Ok(q) => q,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+    let mut attempts = match data.attempts.write() {
+        Ok(a) => a,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+
+    let (quiz_id, attempt_id) = path.into_inner();
+    let Some(quiz) = quizzes.iter().find(|q| q.id == quiz_id) else {
+        return HttpResponse::NotFound().json(ErrorResponse { error: "Quiz not found".to_string() });
+    };
+    let Some(attempt) = attempts.get_mut(&attempt_id).filter(|a| a.quiz_id == quiz_id) else {
+        return HttpResponse::NotFound().json(ErrorResponse { error: "Attempt not found".to_string() });
+    };
+
+    // Finishing is idempotent: a finished attempt just returns its result again
+    let expired = attempt.expire_if_overdue(Utc::now());
+    let just_finished = !attempt.is_finished();
+    if just_finished {
+        attempt.status = AttemptStatus::Finished;
+        attempt.finished_at = Some(Utc::now());
+    }
+    if expired || just_finished {
+        data.settle_attempt(&quizzes, &attempts, attempt_id);
+    }
+
+    HttpResponse::Ok().json(score_attempt(quiz, &attempts[&attempt_id]))
+}
+
+#[derive(serde::Deserialize, utoipa::IntoParams)]
+pub struct UserParams {
+    /// Learner identity
+    pub user_email: String,
+}
+
+#[utoipa::path(
+    get,
+    path = "/me/progress",
+    tag = "Learner",
+    params(
+        UserParams
+    ),
+    responses(
+        (status = 200, description = "Accuracy and coverage per tag and category", body = ProgressReport),
+        (status = 422, description = "Invalid query parameters", body = ValidationErrorResponse),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn get_progress(
+    data: web::Data<AppState>,
+    params: web::Query<UserParams>,
+) -> impl Responder {
+    let quizzes = match data.quizzes.read() {
+        Ok(q) => q,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+    let categories = match data.categories.read() {
+        Ok(c) => c,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+    let history = match data.history.read() {
+        Ok(h) => h,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+
+    let records = history.get(&learner_key(&params.user_email)).map(Vec::as_slice).unwrap_or_default();
+    HttpResponse::Ok().json(build_report(&params.user_email, records, &quizzes, &categories))
+}
+
+fn seeded_rng(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    }
+}
+
+#[derive(serde::Deserialize, utoipa::IntoParams)]
+pub struct RandomQuestionParams {
+    /// Comma-separated tags; questions from quizzes with any of them
+    pub tags: Option<String>,
+    pub category_id: Option<Id>,
+    #[param(inline)]
+    pub difficulty: Option<Difficulty>,
+    /// Makes the pick reproducible for the same pool
+    pub seed: Option<u64>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/questions/random",
+    tag = "Consumption",
+    params(
+        RandomQuestionParams
+    ),
+    responses(
+        (status = 200, description = "Random question and the quiz it belongs to", body = RandomQuestionResponse),
+        (status = 404, description = "No questions match", body = ErrorResponse),
+        (status = 422, description = "Invalid query parameters", body = ValidationErrorResponse),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn get_random_question(
+    data: web::Data<AppState>,
+    params: web::Query<RandomQuestionParams>,
+) -> impl Responder {
+    let quizzes = match data.quizzes.read() {
+        Ok(q) => q,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+
+    let filter = QuestionFilter {
+        tags: parse_tag_list(params.tags.as_deref()),
+        category_id: params.category_id,
+        difficulty: params.difficulty,
+    };
+    let pool = question_pool(&quizzes, &filter);
+    let mut rng = seeded_rng(params.seed);
+    match pool.choose(&mut rng) {
+        Some((quiz, question)) => HttpResponse::Ok().json(RandomQuestionResponse {
+            quiz_id: quiz.id,
+            question: (*question).clone(),
+        }),
+        None => HttpResponse::NotFound().json(ErrorResponse { error: "No questions found".to_string() }),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/quizzes/generate",
+    request_body = GenerateQuizRequest,
+    tag = "Consumption",
+    responses(
+        (status = 201, description = "Generated quiz, retrievable and attemptable by its ID", body = Quiz),
+        (status = 404, description = "No questions match", body = ErrorResponse),
+        (status = 422, description = "Invalid fields", body = ValidationErrorResponse),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn generate_quiz(
+    data: web::Data<AppState>,
+    req: web::Json<GenerateQuizRequest>,
+) -> impl Responder {
+    if let Err(response) = check_valid(&*req) {
+        return response;
+    }
+
+    let mut quizzes = match data.quizzes.write() {
+        Ok(q) => q,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+
+    let filter = QuestionFilter {
+        tags: tags::normalize_all(req.tags.iter().flatten()),
+        category_id: req.category_id,
+        difficulty: req.difficulty,
+    };
+    let pool = question_pool(&quizzes, &filter);
+    let mut rng = seeded_rng(req.seed);
+    let questions: Vec<Question> = draw_questions(&pool, req.count, &mut rng).into_iter().cloned().collect();
+    if questions.is_empty() {
+        return HttpResponse::NotFound().json(ErrorResponse { error: "No questions found".to_string() });
+    }
+
+    // Tag the quiz with the tags of the quizzes its questions came from
+    let tags = source_tags(&pool, &questions);
+
+    let new_quiz = Quiz {
+        id: Id::new(),
+        title: req.title.clone().unwrap_or_else(|| "Generated Quiz".to_string()),
+        category_id: req.category_id,
+        questions,
+        tags,
+        difficulty: req.difficulty,
+        reveal_policy: Default::default(),
+        time_limit_seconds: None,
+        exam_mode: false,
+        shuffle_options: true,
+        shuffle_questions: false,
+        generated: true,
+    };
+
+    data.store_generated(&mut quizzes, new_quiz.clone());
+    HttpResponse::Created().json(new_quiz)
+}
+
+/// Default and maximum number of cards returned by the review queue.
+const DEFAULT_REVIEW_LIMIT: usize = 20;
+const MAX_REVIEW_LIMIT: usize = 100;
+
+#[derive(serde::Deserialize, utoipa::IntoParams)]
+pub struct ReviewQueueParams {
+    /// Learner identity
+    pub user_email: String,
+    /// Maximum number of due cards to return (default 20, max 100)
+    pub limit: Option<usize>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/me/review",
+    tag = "Learner",
+    params(
+        ReviewQueueParams
+    ),
+    responses(
+        (status = 200, description = "Questions due for review, most overdue first", body = ReviewQueue),
+        (status = 422, description = "Invalid query parameters", body = ValidationErrorResponse),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn get_review_queue(
+    data: web::Data<AppState>,
+    params: web::Query<ReviewQueueParams>,
+) -> impl Responder {
+    let quizzes = match data.quizzes.read() {
+        Ok(q) => q,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+    let reviews = match data.reviews.read() {
+        Ok(r) => r,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+
+    let now = Utc::now();
+    let limit = params.limit.unwrap_or(DEFAULT_REVIEW_LIMIT).clamp(1, MAX_REVIEW_LIMIT);
+    let cards: Vec<_> = reviews.get(&learner_key(&params.user_email))
+        .map(|cards| cards.values().collect())
+        .unwrap_or_default();
+
+    // Cards whose question has since been deleted are skipped
+    let mut due: Vec<ReviewItem> = cards.iter()
+        .filter(|card| card.due_at <= now)
+        .filter_map(|card| {
+            let quiz = quizzes.iter().find(|q| q.id == card.quiz_id)?;
+            let question = quiz.questions.iter().find(|q| q.id == card.question_id)?;
+            Some(ReviewItem { card: (*card).clone(), question: question.clone() })
+        })
+        .collect();
+    due.sort_by_key(|item| item.card.due_at);
+    let due_count = due.len();
+    due.truncate(limit);
+
+    HttpResponse::Ok().json(ReviewQueue {
+        due_count,
+        items: due,
+        next_due_at: cards.iter().map(|c| c.due_at).filter(|due_at| *due_at > now).min(),
+    })
+}
+
+#[utoipa::path(
+    post,
+    path = "/me/review",
+    request_body = ReviewGradeRequest,
+    tag = "Learner",
+    responses(
+        (status = 200, description = "Updated review schedule", body = ReviewCard),
+        (status = 404, description = "Question not found", body = ErrorResponse),
+        (status = 422, description = "Invalid fields", body = ValidationErrorResponse),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn grade_review(
+    data: web::Data<AppState>,
+    req: web::Json<ReviewGradeRequest>,
+) -> impl Responder {
+    if let Err(response) = check_valid(&*req) {
+        return response;
+    }
+
+    let quiz_id = match data.question_index.read() {
+        Ok(index) => index.get(&req.question_id).and_then(|owners| owners.first()).map(|(id, _)| *id),
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+    let Some(quiz_id) = quiz_id else {
+        return HttpResponse::NotFound().json(ErrorResponse { error: "Question not found".to_string() });
+    };
+
+    match data.grade_review(&req.user_email, quiz_id, req.question_id, req.grade) {
+        Some(card) => HttpResponse::Ok().json(card),
+        None => HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    }
+}
+
+/// Default and maximum number of questions in a practice session.
+const DEFAULT_PRACTICE_LENGTH: usize = 10;
+const MAX_PRACTICE_LENGTH: usize = 50;
+
+#[utoipa::path(
+    post,
+    path = "/practice/sessions",
+    request_body = StartPracticeRequest,
+    tag = "Learner",
+    responses(
+        (status = 201, description = "Practice session started", body = PracticeSession),
+        (status = 422, description = "Invalid fields", body = ValidationErrorResponse),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn start_practice(
+    data: web::Data<AppState>,
+    req: web::Json<StartPracticeRequest>,
+) -> impl Responder {
+    if let Err(response) = check_valid(&*req) {
+        return response;
+    }
+    let req = req.into_inner();
+
+    let session = PracticeSession {
+        id: Id::new(),
+        user_email: req.user_email,
+        tags: tags::normalize_all(&req.tags),
+        category_id: req.category_id,
+        length: req.length.unwrap_or(DEFAULT_PRACTICE_LENGTH).clamp(1, MAX_PRACTICE_LENGTH),
+        asked: Vec::new(),
+        correct_count: 0,
+        started_at: Utc::now(),
+        current: None,
+    };
+    match data.practice_sessions.write() {
+        Ok(mut sessions) => {
+            sessions.insert(session.id, session.clone());
+            HttpResponse::Created().json(session)
+        }
+        Err(_) => HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/practice/sessions/{id}",
+    tag = "Learner",
+    params(
+        ("id" = Id, Path, description = "Practice session ID")
+    ),
+    responses(
+        (status = 200, description = "Practice session", body = PracticeSession),
+        (status = 404, description = "Session not found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn get_practice(
+    data: web::Data<AppState>,
+    path: web::Path<Id>,
+) -> impl Responder {
+    let sessions = match data.practice_sessions.read() {
+        Ok(s) => s,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+    match sessions.get(&path.into_inner()) {
+        Some(session) => HttpResponse::Ok().json(session),
+        None => HttpResponse::NotFound().json(ErrorResponse { error: "Session not found".to_string() }),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/practice/sessions/{id}/next",
+    tag = "Learner",
+    params(
+        ("id" = Id, Path, description = "Practice session ID")
+    ),
+    responses(
+        (status = 200, description = "Next question, matched to the learner's ability. Repeats the current question until it is answered", body = PracticeQuestion),
+        (status = 204, description = "Session complete or no questions left"),
+        (status = 404, description = "Session not found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn next_practice_question(
+    data: web::Data<AppState>,
+    path: web::Path<Id>,
+) -> impl Responder {
+    let quizzes = match data.quizzes.read() {
+        Ok(q) => q,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+    let mut sessions = match data.practice_sessions.write() {
+        Ok(s) => s,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+    let ratings = match data.question_ratings.read() {
+        Ok(r) => r,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+
+    let Some(session) = sessions.get_mut(&path.into_inner()) else {
+        return HttpResponse::NotFound().json(ErrorResponse { error: "Session not found".to_string() });
+    };
+    let ability = match data.abilities.read() {
+        Ok(a) => a.get(&learner_key(&session.user_email)).copied().unwrap_or(adaptive::INITIAL_ABILITY),
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+    let rating_of = |quiz: &Quiz, question: &Question| ratings.get(&question.id).copied()
+        .unwrap_or_else(|| QuestionRating::prior(quiz.question_difficulty(question)))
+        .rating;
+
+    let next = match session.current {
+        Some((quiz_id, question_id)) => quizzes.iter()
+            .find(|q| q.id == quiz_id)
+            .and_then(|quiz| quiz.questions.iter().find(|q| q.id == question_id).map(|q| (quiz, q, rating_of(quiz, q)))),
+        None if session.asked.len() >= session.length => None,
+        None => {
+            let filter = QuestionFilter {
+                tags: session.tags.clone(),
+                category_id: session.category_id,
+                difficulty: None,
+            };
+            let candidates = question_pool(&quizzes, &filter).into_iter()
+                .filter(|(_, question)| !session.asked.contains(&question.id))
+                .map(|(quiz, question)| (quiz, question, rating_of(quiz, question)));
+            adaptive::pick_next(candidates, ability)
+        }
+    };
+    let Some((quiz, question, question_rating)) = next else {
+        session.current = None;
+        return HttpResponse::NoContent().finish();
+    };
+
+    if session.current.is_none() {
+        session.asked.push(question.id);
+        session.current = Some((quiz.id, question.id));
+    }
+    HttpResponse::Ok().json(PracticeQuestion {
+        session_id: session.id,
+        quiz_id: quiz.id,
+        number: session.asked.len(),
+        question: question.clone(),
+        ability,
+        question_rating,
+        expected_success: adaptive::expected_success(ability, question_rating),
+    })
+}
+
+#[utoipa::path(
+    post,
+    path = "/practice/sessions/{id}/answers",
+    request_body = PracticeAnswerRequest,
+    tag = "Learner",
+    params(
+        ("id" = Id, Path, description = "Practice session ID")
+    ),
+    responses(
+        (status = 200, description = "Answer result with the updated ability rating", body = PracticeAnswerResponse),
+        (status = 404, description = "Session, question or option not found", body = ErrorResponse),
+        (status = 409, description = "Question is not the one currently served", body = ErrorResponse),
+        (status = 422, description = "Invalid fields", body = ValidationErrorResponse),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn submit_practice_answer(
+    data: web::Data<AppState>,
+    path: web::Path<Id>,
+    req: web::Json<PracticeAnswerRequest>,
+) -> impl Responder {
+    if let Err(response) = check_valid(&*req) {
+        return response;
+    }
+    let quizzes = match data.quizzes.read() {
+        Ok(q) => q,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+    let mut sessions = match data.practice_sessions.write() {
+        Ok(s) => s,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+
+    let Some(session) = sessions.get_mut(&path.into_inner()) else {
+        return HttpResponse::NotFound().json(ErrorResponse { error: "Session not found".to_string() });
+    };
+    let Some((quiz_id, question_id)) = session.current.filter(|(_, question_id)| *question_id == req.question_id) else {
+        return HttpResponse::Conflict().json(ErrorResponse { error: "Question is not the one currently served".to_string() });
+    };
+    let Some((quiz, question)) = quizzes.iter()
+        .find(|q| q.id == quiz_id)
+        .and_then(|quiz| quiz.questions.iter().find(|q| q.id == question_id).map(|q| (quiz, q)))
+    else {
+        return HttpResponse::NotFound().json(ErrorResponse { error: "Question not found".to_string() });
+    };
+    let Some(option) = question.options.iter().find(|o| o.id == req.option_id) else {
+        return HttpResponse::NotFound().json(ErrorResponse { error: "Option not found".to_string() });
+    };
+
+    session.current = None;
+    if option.is_correct {
+        session.correct_count += 1;
+    }
+    let ability = data.rate_answer(Some(&session.user_email), question.id, quiz.question_difficulty(question), option.is_correct);
+    data.record_answer(&quizzes, &session.user_email, AnswerRecord {
+        quiz_id,
+        question_id,
+        option_id: option.id,
+        correct: option.is_correct,
+        answered_at: Utc::now(),
+    });
+
+    HttpResponse::Ok().json(PracticeAnswerResponse {
+        answer: answer_response(quiz, question, option, 0),
+        ability,
+        remaining: session.length - session.asked.len(),
+    })
+}
+
+/// Default and maximum leaderboard page sizes.
+const DEFAULT_LEADERBOARD_PAGE_SIZE: usize = 20;
+const MAX_LEADERBOARD_PAGE_SIZE: usize = 100;
+
+#[derive(serde::Deserialize, utoipa::IntoParams)]
+pub struct LeaderboardParams {
+    /// Rank a single quiz
+    pub quiz_id: Option<Id>,
+    /// Rank quizzes with this tag; global when neither quiz_id nor tag is given
+    pub tag: Option<String>,
+    #[param(inline)]
+    pub window: Option<LeaderboardWindow>,
+    pub page: Option<usize>,
+    /// Entries per page (default 20, max 100)
+    pub per_page: Option<usize>,
+    /// Learner to look up; required for `/leaderboards/me`
+    pub user_email: Option<String>,
+}
+
+/// Ranks learners by finished attempts matching the leaderboard scope.
+fn leaderboard_entries(data: &AppState, params: &LeaderboardParams) -> Result<Vec<LeaderboardEntry>, HttpResponse> {
+    if params.quiz_id.is_some() && params.tag.is_some() {
+        return Err(HttpResponse::BadRequest().json(ErrorResponse { error: "Use either quiz_id or tag, not both".to_string() }));
+    }
+    // Attempts that ran out of time since the last sweep still count
+    data.expire_overdue_attempts();
+
+    let quizzes = data.quizzes.read()
+        .map_err(|_| HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }))?;
+    let attempts = data.attempts.read()
+        .map_err(|_| HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }))?;
+
+    let tag = params.tag.as_deref().and_then(tags::normalize);
+    let since = leaderboard::window_start(params.window.unwrap_or_default(), Utc::now());
+    let keys: Vec<(String, &Attempt)> = attempts.values()
+        .filter(|a| a.is_finished())
+        .filter(|a| since.is_none_or(|since| a.finished_at.is_some_and(|at| at >= since)))
+        .filter_map(|a| a.user_email.as_deref().map(|email| (learner_key(email), a)))
+        .collect();
+    let results = keys.iter().filter_map(|(user, attempt)| {
+        let quiz = quizzes.iter().find(|q| q.id == attempt.quiz_id)?;
+        let in_scope = match (&params.quiz_id, &tag) {
+            (Some(quiz_id), _) => quiz.id == *quiz_id,
+            // Generated and daily quizzes are free to repeat, so only boards
+            // for that exact quiz rank them
+            (None, Some(tag)) => !quiz.generated && quiz.tags.contains(tag),
+            (None, None) => !quiz.generated,
+        };
+        in_scope.then(|| (user.as_str(), score_attempt(quiz, attempt)))
+    });
+    Ok(leaderboard::rank(results))
+}
+
+#[utoipa::path(
+    get,
+    path = "/leaderboards",
+    tag = "Consumption",
+    params(
+        LeaderboardParams
+    ),
+    responses(
+        (status = 200, description = "Page of the ranking", body = Leaderboard),
+        (status = 400, description = "Both quiz_id and tag given", body = ErrorResponse),
+        (status = 422, description = "Invalid query parameters", body = ValidationErrorResponse),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn get_leaderboard(
+    data: web::Data<AppState>,
+    params: web::Query<LeaderboardParams>,
+) -> impl Responder {
+    let entries = match leaderboard_entries(&data, &params) {
+        Ok(entries) => entries,
+        Err(response) => return response,
+    };
+
+    let page = params.page.unwrap_or(1).max(1);
+    let per_page = params.per_page.unwrap_or(DEFAULT_LEADERBOARD_PAGE_SIZE).clamp(1, MAX_LEADERBOARD_PAGE_SIZE);
+    let total = entries.len();
+    let entries = entries.into_iter()
+        .skip((page - 1).saturating_mul(per_page))
+        .take(per_page)
+        .map(|entry| LeaderboardEntry { user_email: leaderboard::mask_email(&entry.user_email), ..entry })
+        .collect();
+
+    HttpResponse::Ok().json(Leaderboard {
+        window: params.window.unwrap_or_default(),
+        quiz_id: params.quiz_id,
+        tag: params.tag.clone(),
+        total,
+        page,
+        per_page,
+        entries,
+    })
+}
+
+#[utoipa::path(
+    get,
+    path = "/leaderboards/me",
+    tag = "Consumption",
+    params(
+        LeaderboardParams
+    ),
+    responses(
+        (status = 200, description = "The learner's own standing", body = LeaderboardEntry),
+        (status = 400, description = "Missing user_email, or both quiz_id and tag given", body = ErrorResponse),
+        (status = 404, description = "Learner has no finished attempts in this leaderboard", body = ErrorResponse),
+        (status = 422, description = "Invalid query parameters", body = ValidationErrorResponse),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn get_my_rank(
+    data: web::Data<AppState>,
+    params: web::Query<LeaderboardParams>,
+) -> impl Responder {
+    let Some(user_email) = params.user_email.as_deref().map(learner_key) else {
+        return HttpResponse::BadRequest().json(ErrorResponse { error: "user_email is required".to_string() });
+    };
+    let entries = match leaderboard_entries(&data, &params) {
+        Ok(entries) => entries,
+        Err(response) => return response,
+    };
+
+    match entries.into_iter().find(|e| e.user_email == user_email) {
+        Some(entry) => HttpResponse::Ok().json(LeaderboardEntry { user_email: leaderboard::mask_email(&entry.user_email), ..entry }),
+        None => HttpResponse::NotFound().json(ErrorResponse { error: "Not ranked".to_string() }),
+    }
+}
+
+#[derive(serde::Deserialize, utoipa::IntoParams)]
+pub struct DailyParams {
+    /// UTC offset such as `+02:00` deciding which day it is; defaults to UTC
+    pub tz: Option<String>,
+    /// Learner whose attempt or streak to include
+    pub user_email: Option<String>,
+}
+
+/// The offset deciding which day it is. Once a learner has started a daily
+/// attempt their offset is pinned: omitting `tz` uses it, and any other `tz` is
+/// rejected so switching offsets can't skip or repeat days.
+fn daily_offset(data: &AppState, user_email: Option<&str>, tz: Option<&str>) -> Result<FixedOffset, HttpResponse> {
+    let pinned = match user_email {
+        Some(email) => data.daily_offsets.read()
+            .map_err(|_| HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }))?
+            .get(&learner_key(email))
+            .copied(),
+        None => None,
+    };
+    if let (Some(pinned), None) = (pinned, tz) {
+        return Ok(pinned);
+    }
+    let Some(offset) = daily::parse_offset(tz) else {
+        return Err(HttpResponse::BadRequest().json(ErrorResponse { error: "tz must be a UTC offset such as +02:00".to_string() }));
+    };
+    match pinned {
+        Some(pinned) if pinned != offset => Err(offset_conflict(pinned)),
+        _ => Ok(offset),
+    }
+}
+
+fn offset_conflict(pinned: FixedOffset) -> HttpResponse {
+    HttpResponse::Conflict().json(ErrorResponse { error: format!("tz is fixed to {} by the learner's first daily attempt", pinned) })
+}
+
+/// The challenge quiz for a date. Existing challenges are served under the read
+/// lock; the write lock is only taken to create a missing one.
+fn daily_challenge(data: &AppState, date: NaiveDate) -> Result<Quiz, HttpResponse> {
+    {
+        let quizzes = data.quizzes.read()
+            .map_err(|_| HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }))?;
+        let daily_quizzes = data.daily_quizzes.read()
+            .map_err(|_| HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }))?;
+        if let Some(quiz) = daily_quizzes.get(&date).and_then(|id| quizzes.iter().find(|q| q.id == *id)) {
+            return Ok(quiz.clone());
+        }
+    }
+
+    let mut quizzes = data.quizzes.write()
+        .map_err(|_| HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }))?;
+    data.daily_quiz(&mut quizzes, date)
+        .and_then(|id| quizzes.iter().find(|q| q.id == id))
+        .cloned()
+        .ok_or_else(|| HttpResponse::NotFound().json(ErrorResponse { error: "No questions found".to_string() }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/daily",
+    tag = "Consumption",
+    params(
+        DailyParams
+    ),
+    responses(
+        (status = 200, description = "Today's challenge, and the learner's attempt if started", body = DailyChallenge),
+        (status = 400, description = "Invalid timezone", body = ErrorResponse),
+        (status = 404, description = "No questions available", body = ErrorResponse),
+        (status = 409, description = "tz differs from the learner's pinned offset", body = ErrorResponse),
+        (status = 422, description = "Invalid query parameters", body = ValidationErrorResponse),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn get_daily_challenge(
+    data: web::Data<AppState>,
+    params: web::Query<DailyParams>,
+) -> impl Responder {
+    let offset = match daily_offset(&data, params.user_email.as_deref(), params.tz.as_deref()) {
+        Ok(offset) => offset,
+        Err(response) => return response,
+    };
+    let date = daily::today(offset, Utc::now());
+    let mut quiz = match daily_challenge(&data, date) {
+        Ok(quiz) => quiz,
+        Err(response) => return response,
+    };
+
+    let attempt = match &params.user_email {
+        Some(email) => {
+            let attempts = match data.attempts.read() {
+                Ok(a) => a,
+                Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+            };
+            let daily_attempts = match data.daily_attempts.read() {
+                Ok(d) => d,
+                Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+            };
+            daily_attempts.get(&learner_key(email))
+                .and_then(|dates| dates.get(&date))
+                .and_then(|id| attempts.get(id))
+                .cloned()
+        }
+        None => None,
+    };
+
+    // A learner who started the challenge sees it as their attempt presents it
+    if let Some(attempt) = &attempt {
+        quiz.questions = attempt_questions(&quiz, attempt.id);
+    }
+    if quiz.exam_mode && !attempt.as_ref().is_some_and(Attempt::is_finished) {
+        withhold_explanations(&mut quiz.questions);
+    }
+    HttpResponse::Ok().json(DailyChallenge { date, quiz, attempt })
+}
+
+#[utoipa::path(
+    post,
+    path = "/daily/attempts",
+    request_body = StartDailyRequest,
+    tag = "Consumption",
+    responses(
+        (status = 201, description = "Attempt at today's challenge started; answer and finish it like any attempt", body = Attempt),
+        (status = 404, description = "No questions available", body = ErrorResponse),
+        (status = 409, description = "Today's challenge was already attempted, or tz differs from the learner's pinned offset", body = ErrorResponse),
+        (status = 422, description = "Invalid fields", body = ValidationErrorResponse),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn start_daily_attempt(
+    data: web::Data<AppState>,
+    req: web::Json<StartDailyRequest>,
+) -> impl Responder {
+    if let Err(response) = check_valid(&*req) {
+        return response;
+    }
+    let offset = match daily_offset(&data, Some(&req.user_email), req.tz.as_deref()) {
+        Ok(offset) => offset,
+        Err(response) => return response,
+    };
+    let date = daily::today(offset, Utc::now());
+    let quiz = match daily_challenge(&data, date) {
+        Ok(quiz) => quiz,
+        Err(response) => return response,
+    };
+    let mut attempts = match data.attempts.write() {
+        Ok(a) => a,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+    let mut daily_attempts = match data.daily_attempts.write() {
+        Ok(d) => d,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+
+    let mut offsets = match data.daily_offsets.write() {
+        Ok(o) => o,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+
+    let user = learner_key(&req.user_email);
+    // Another request may have pinned a different offset since it was read
+    if let Some(&pinned) = offsets.get(&user) && pinned != offset {
+        return offset_conflict(pinned);
+    }
+    let dates = daily_attempts.entry(user.clone()).or_default();
+    if dates.contains_key(&date) {
+        return HttpResponse::Conflict().json(ErrorResponse { error: "Today's challenge was already attempted".to_string() });
+    }
+    let attempt = Attempt::new(&quiz, Some(req.user_email.clone()));
+    dates.insert(date, attempt.id);
+    offsets.insert(user, offset);
+    attempts.insert(attempt.id, attempt.clone());
+
+    HttpResponse::Created().json(attempt)
+}
+
+#[utoipa::path(
+    get,
+    path = "/daily/streak",
+    tag = "Learner",
+    params(
+        DailyParams
+    ),
+    responses(
+        (status = 200, description = "Streak of consecutive days with a finished challenge", body = DailyStreak),
+        (status = 400, description = "Missing user_email or invalid timezone", body = ErrorResponse),
+        (status = 409, description = "tz differs from the learner's pinned offset", body = ErrorResponse),
+        (status = 422, description = "Invalid query parameters", body = ValidationErrorResponse),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn get_daily_streak(
+    data: web::Data<AppState>,
+    params: web::Query<DailyParams>,
+) -> impl Responder {
+    let Some(user_email) = params.user_email.as_deref().map(learner_key) else {
+        return HttpResponse::BadRequest().json(ErrorResponse { error: "user_email is required".to_string() });
+    };
+    let offset = match daily_offset(&data, Some(&user_email), params.tz.as_deref()) {
+        Ok(offset) => offset,
+        Err(response) => return response,
+    };
+    let today = daily::today(offset, Utc::now());
+
+    let attempts = match data.attempts.read() {
+        Ok(a) => a,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+    let daily_attempts = match data.daily_attempts.read() {
+        Ok(d) => d,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+
+    let completed = daily_attempts.get(&user_email)
+        .map(|dates| daily::completed_days(dates, &attempts))
+        .unwrap_or_default();
+    let (current, longest) = daily::streaks(&completed, today);
+
+    HttpResponse::Ok().json(DailyStreak {
+        user_email,
+        current,
+        longest,
+        completed_today: completed.contains(&today),
+        last_completed: completed.last().copied(),
+    })
+}
+
+#[utoipa::path(
+    get,
+    path = "/me/achievements",
+    tag = "Learner",
+    params(
+        UserParams
+    ),
+    responses(
+        (status = 200, description = "Badges earned and still locked", body = AchievementsReport),
+        (status = 422, description = "Invalid query parameters", body = ValidationErrorResponse),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn get_achievements(
+    data: web::Data<AppState>,
+    params: web::Query<UserParams>,
+) -> impl Responder {
+    let learners = match data.achievements.read() {
+        Ok(a) => a,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+
+    let user_email = learner_key(&params.user_email);
+    let earned = learners.get(&user_email).map(|l| l.badges.clone()).unwrap_or_default();
+    let locked = data.achievement_rules.iter()
+        .filter(|rule| !earned.iter().any(|b| b.id == rule.id))
+        .map(AchievementInfo::from)
+        .collect();
+
+    HttpResponse::Ok().json(AchievementsReport { user_email, earned, locked })
+}
+
+/// Default and maximum number of search results.
+const DEFAULT_SEARCH_LIMIT: usize = 20;
+const MAX_SEARCH_LIMIT: usize = 100;
+
+#[derive(serde::Deserialize, utoipa::IntoParams)]
+pub struct SearchParams {
+    /// Search terms; code identifiers such as `Array.prototype.map` are matched whole and by parts
+    pub q: String,
+    /// Maximum number of results (default 20, max 100)
+    pub limit: Option<usize>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/search",
+    tag = "Consumption",
+    params(
+        SearchParams
+    ),
+    responses(
+        (status = 200, description = "Quizzes and questions ranked by relevance, with highlighted matches", body = SearchResults),
+        (status = 400, description = "Query has no searchable terms", body = ErrorResponse),
+        (status = 422, description = "Invalid query parameters", body = ValidationErrorResponse),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn search(
+    data: web::Data<AppState>,
+    params: web::Query<SearchParams>,
+) -> impl Responder {
+    if full_text::tokenize(&params.q).is_empty() {
+        return HttpResponse::BadRequest().json(ErrorResponse { error: "q must contain searchable terms".to_string() });
+    }
+    let quizzes = match data.quizzes.read() {
+        Ok(q) => q,
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+
+    let limit = params.limit.unwrap_or(DEFAULT_SEARCH_LIMIT).clamp(1, MAX_SEARCH_LIMIT);
+    let cached = match data.search_index.read() {
+        Ok(index) => index.as_ref().map(|index| index.search(&params.q, limit)),
+        Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+    };
+    // The index is rebuilt lazily after quizzes change
+    let (total, results) = match cached {
+        Some(found) => found,
+        None => match data.search_index.write() {
+            Ok(mut index) => index.get_or_insert_with(|| SearchIndex::build(&quizzes)).search(&params.q, limit),
+            Err(_) => return HttpResponse::InternalServerError().json(ErrorResponse { error: "Lock poisoned".to_string() }),
+        },
+    };
 
-    HttpResponse::Ok().json(quiz)
+    HttpResponse::Ok().json(SearchResults { query: params.q.clone(), total, results })
 }
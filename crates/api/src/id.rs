@@ -44,7 +44,7 @@ impl fmt::Display for Id {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // Convert back to TSID for string representation
         let tsid = TSID::from(self.0 as u64);
-        write!(f, "{}", tsid.to_string())
+        write!(f, "{}", tsid)
     }
 }
 
@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+use chrono::{DateTime, Datelike, Duration, Utc};
+use crate::id::Id;
+use crate::models::{AttemptResult, LeaderboardEntry, LeaderboardWindow};
+
+/// Earliest finish time counted by a window; `None` counts everything.
+/// Weeks start on Monday, both windows in UTC.
+pub fn window_start(window: LeaderboardWindow, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let midnight = now.date_naive().and_hms_opt(0, 0, 0)?.and_utc();
+    match window {
+        LeaderboardWindow::Daily => Some(midnight),
+        LeaderboardWindow::Weekly => Some(midnight - Duration::days(now.weekday().num_days_from_monday().into())),
+        LeaderboardWindow::AllTime => None,
+    }
+}
+
+/// Ranks learners by their best result per quiz, summed over quizzes.
+/// Higher scores rank first; equal scores go to the faster learner. Learners
+/// tied on both share a rank.
+pub fn rank<'a>(results: impl IntoIterator<Item = (&'a str, AttemptResult)>) -> Vec<LeaderboardEntry> {
+    let mut best: HashMap<(&str, Id), AttemptResult> = HashMap::new();
+    for (user, result) in results {
+        let key = (user, result.quiz_id);
+        let better = best.get(&key).is_none_or(|current| {
+            result.score > current.score
+                || (result.score == current.score && result.duration_seconds < current.duration_seconds)
+        });
+        if better {
+            best.insert(key, result);
+        }
+    }
+
+    let mut totals: HashMap<&str, LeaderboardEntry> = HashMap::new();
+    for ((user, _), result) in best {
+        let entry = totals.entry(user).or_insert_with(|| LeaderboardEntry {
+            rank: 0,
+            user_email: user.to_string(),
+            score: 0.0,
+            max_score: 0.0,
+            quizzes: 0,
+            duration_seconds: 0,
+        });
+        entry.score += result.score;
+        entry.max_score += result.max_score;
+        entry.quizzes += 1;
+        entry.duration_seconds += result.duration_seconds;
+    }
+
+    let mut entries: Vec<LeaderboardEntry> = totals.into_values().collect();
+    entries.sort_by(|a, b| b.score.total_cmp(&a.score)
+        .then(a.duration_seconds.cmp(&b.duration_seconds))
+        .then_with(|| a.user_email.cmp(&b.user_email)));
+    for i in 0..entries.len() {
+        entries[i].rank = match i.checked_sub(1).map(|prev| &entries[prev]) {
+            Some(prev) if prev.score == entries[i].score && prev.duration_seconds == entries[i].duration_seconds => prev.rank,
+            _ => i + 1,
+        };
+    }
+    entries
+}
+
+/// Hides all but the first character of an email's local part, so public
+/// boards don't publish addresses: `carol@example.com` becomes `c***@example.com`.
+pub fn mask_email(email: &str) -> String {
+    let (local, domain) = email.split_once('@').unwrap_or((email, ""));
+    let first: String = local.chars().take(1).collect();
+    if domain.is_empty() {
+        format!("{first}***")
+    } else {
+        format!("{first}***@{domain}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(quiz_id: Id, score: f64, duration_seconds: i64) -> AttemptResult {
+        let now = Utc::now();
+        AttemptResult {
+            attempt_id: Id::new(),
+            quiz_id,
+            score,
+            max_score: 3.0,
+            correct_count: score as usize,
+            total_questions: 3,
+            started_at: now,
+            finished_at: now,
+            duration_seconds,
+            breakdown: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn ranks_by_best_score_then_duration() {
+        let (a, b) = (Id::new(), Id::new());
+        let entries = rank([
+            ("slow", result(a, 3.0, 90)),
+            ("fast", result(a, 3.0, 30)),
+            ("fast", result(a, 1.0, 10)),
+            ("twice", result(a, 2.0, 10)),
+            ("twice", result(b, 2.0, 10)),
+            ("tied", result(a, 3.0, 30)),
+        ]);
+
+        let order: Vec<(&str, usize)> = entries.iter().map(|e| (e.user_email.as_str(), e.rank)).collect();
+        assert_eq!(order, [("twice", 1), ("fast", 2), ("tied", 2), ("slow", 4)]);
+        assert_eq!(entries[0].quizzes, 2);
+        assert_eq!(entries[1].duration_seconds, 30);
+    }
+
+    #[test]
+    fn masks_emails() {
+        assert_eq!(mask_email("carol@example.com"), "c***@example.com");
+        assert_eq!(mask_email("x"), "x***");
+        assert_eq!(mask_email("@example.com"), "***@example.com");
+    }
+
+    #[test]
+    fn windows_start_at_utc_midnight_and_monday() {
+        // A Thursday afternoon
+        let now = DateTime::parse_from_rfc3339("2024-05-16T15:30:00Z").unwrap().with_timezone(&Utc);
+        assert_eq!(window_start(LeaderboardWindow::Daily, now).unwrap().to_rfc3339(), "2024-05-16T00:00:00+00:00");
+        assert_eq!(window_start(LeaderboardWindow::Weekly, now).unwrap().to_rfc3339(), "2024-05-13T00:00:00+00:00");
+        assert!(window_start(LeaderboardWindow::AllTime, now).is_none());
+    }
+}
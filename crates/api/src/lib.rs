@@ -1,14 +1,27 @@
 use actix_web::{web, App, HttpServer, middleware};
 use actix_web::dev::Server;
 use std::net::TcpListener;
-use std::sync::RwLock;
+use std::time::Duration;
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 use crate::models::{
     CreateQuizRequest, Quiz, Question, QuestionOption, 
-    SubmitAnswerRequest, AnswerResponse,
-    Category, CreateCategoryRequest, CreateQuestionRequest, CreateOptionRequest,
-    UpdateQuizRequest, PaginationParams, ErrorResponse
+    SubmitAnswerRequest, AnswerResponse, HintResponse,
+    Attempt, AttemptAnswer, AttemptStatus, StartAttemptRequest, AttemptAnswerRequest,
+    AttemptResult, QuestionResult, AnswerRecord, ProgressReport, TopicProgress,
+    Category, CategoryNode, CreateCategoryRequest, UpdateCategoryRequest, CreateQuestionRequest, CreateOptionRequest,
+    UpdateQuizRequest, UpdateQuestionRequest, UpdateOptionRequest, ReorderRequest, PaginationParams, ErrorResponse, Difficulty,
+    GenerateQuizRequest, RandomQuestionResponse,
+    RevealPolicy, RevealedOption,
+    ReviewCard, ReviewItem, ReviewQueue, ReviewGradeRequest,
+    StartPracticeRequest, PracticeSession, PracticeQuestion, PracticeAnswerRequest, PracticeAnswerResponse,
+    Leaderboard, LeaderboardEntry, LeaderboardWindow,
+    DailyChallenge, StartDailyRequest, DailyStreak,
+    Badge, AchievementInfo, AchievementsReport,
+    SearchHitKind, SearchHighlight, SearchHit, SearchResults,
+    TagMode, TagUsage, QuizPage, CategoryPage,
+    QuizSummary, QuizSummaryPage,
+    FieldError, ValidationErrorResponse,
 };
 
 pub mod models;
@@ -16,6 +29,23 @@ pub mod state;
 pub mod handlers;
 pub mod auth; // Empty module
 pub mod id;
+pub mod scoring;
+pub mod progress;
+pub mod selection;
+pub mod srs;
+pub mod adaptive;
+pub mod shuffle;
+pub mod leaderboard;
+pub mod daily;
+pub mod achievements;
+pub mod search;
+pub mod tags;
+pub mod categories;
+pub mod patch;
+pub mod pagination;
+pub mod validation;
+#[cfg(test)]
+mod fixtures;
 
 use state::AppState;
 
@@ -25,60 +55,186 @@ use state::AppState;
         handlers::health_check,
         handlers::create_category,
         handlers::list_categories,
+        handlers::get_category,
+        handlers::get_category_tree,
+        handlers::get_category_path,
+        handlers::update_category,
+        handlers::delete_category,
+        handlers::list_tags,
         handlers::create_quiz,
         handlers::get_quiz,
         handlers::list_quizzes,
         handlers::submit_answer,
         handlers::delete_quiz,
         handlers::update_quiz,
+        handlers::patch_quiz,
+        handlers::add_question,
+        handlers::reorder_questions,
+        handlers::update_question,
+        handlers::delete_question,
+        handlers::add_option,
+        handlers::reorder_options,
+        handlers::update_option,
+        handlers::delete_option,
         handlers::get_random_quiz,
+        handlers::next_hint,
+        handlers::start_attempt,
+        handlers::get_attempt,
+        handlers::get_attempt_questions,
+        handlers::submit_attempt_answer,
+        handlers::finish_attempt,
+        handlers::get_progress,
+        handlers::get_random_question,
+        handlers::generate_quiz,
+        handlers::get_review_queue,
+        handlers::grade_review,
+        handlers::start_practice,
+        handlers::get_practice,
+        handlers::next_practice_question,
+        handlers::submit_practice_answer,
+        handlers::get_leaderboard,
+        handlers::get_my_rank,
+        handlers::get_daily_challenge,
+        handlers::start_daily_attempt,
+        handlers::get_daily_streak,
+        handlers::get_achievements,
+        handlers::search,
     ),
     components(
         schemas(
             CreateQuizRequest, Quiz, Question, QuestionOption, 
-            SubmitAnswerRequest, AnswerResponse,
-            Category, CreateCategoryRequest, CreateQuestionRequest, CreateOptionRequest,
-            UpdateQuizRequest,
-            PaginationParams, ErrorResponse
+            SubmitAnswerRequest, AnswerResponse, HintResponse,
+            Attempt, AttemptAnswer, AttemptStatus, StartAttemptRequest, AttemptAnswerRequest,
+            AttemptResult, QuestionResult, AnswerRecord, ProgressReport, TopicProgress,
+            Category, CategoryNode, CreateCategoryRequest, UpdateCategoryRequest, CreateQuestionRequest, CreateOptionRequest,
+            UpdateQuizRequest, UpdateQuestionRequest, UpdateOptionRequest, ReorderRequest, Difficulty, RevealPolicy, RevealedOption,
+            GenerateQuizRequest, RandomQuestionResponse,
+            ReviewCard, ReviewItem, ReviewQueue, ReviewGradeRequest,
+            StartPracticeRequest, PracticeSession, PracticeQuestion, PracticeAnswerRequest, PracticeAnswerResponse,
+            Leaderboard, LeaderboardEntry, LeaderboardWindow,
+            DailyChallenge, StartDailyRequest, DailyStreak,
+            Badge, AchievementInfo, AchievementsReport,
+            SearchHitKind, SearchHighlight, SearchHit, SearchResults,
+            TagMode, TagUsage, QuizPage, CategoryPage,
+            QuizSummary, QuizSummaryPage,
+            PaginationParams, ErrorResponse, FieldError, ValidationErrorResponse
         )
     ),
     tags(
         (name = "System", description = "System endpoints"),
         (name = "Management", description = "Quiz management endpoints"),
-        (name = "Consumption", description = "Public consumption endpoints")
+        (name = "Consumption", description = "Public consumption endpoints"),
+        (name = "Learner", description = "Per-user progress endpoints")
     )
 )]
 pub struct ApiDoc;
 
-pub fn run(listener: TcpListener, quizzes: Vec<Quiz>, categories: Vec<Category>) -> Result<Server, std::io::Error> {
-    let data = web::Data::new(AppState {
-        quizzes: RwLock::new(quizzes),
-        categories: RwLock::new(categories),
+/// Registers all API routes; shared by the HTTP server and the Lambda entrypoint.
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg
+        .app_data(web::JsonConfig::default().error_handler(handlers::json_error))
+        .app_data(web::QueryConfig::default().error_handler(handlers::query_error))
+        .service(
+            SwaggerUi::new("/swagger-ui/{_:.*}").url("/api-docs/openapi.json", ApiDoc::openapi())
+        )
+        .route("/health", web::get().to(handlers::health_check))
+        .route("/search", web::get().to(handlers::search))
+        .route("/tags", web::get().to(handlers::list_tags))
+        .service(
+            web::scope("/categories")
+                .route("", web::post().to(handlers::create_category))
+                .route("", web::get().to(handlers::list_categories))
+                .route("/tree", web::get().to(handlers::get_category_tree))
+                .route("/{id}", web::get().to(handlers::get_category))
+                .route("/{id}/path", web::get().to(handlers::get_category_path))
+                .route("/{id}", web::put().to(handlers::update_category))
+                .route("/{id}", web::delete().to(handlers::delete_category))
+        )
+        .service(
+            web::scope("/quizzes")
+                .route("", web::post().to(handlers::create_quiz))
+                .route("", web::get().to(handlers::list_quizzes))
+                .route("/random", web::get().to(handlers::get_random_quiz))
+                .route("/generate", web::post().to(handlers::generate_quiz))
+                .route("/{id}", web::get().to(handlers::get_quiz))
+                .route("/{id}", web::put().to(handlers::update_quiz))
+                .route("/{id}", web::patch().to(handlers::patch_quiz))
+                .route("/{id}", web::delete().to(handlers::delete_quiz))
+                .route("/{id}/questions", web::post().to(handlers::add_question))
+                .route("/{id}/questions/order", web::put().to(handlers::reorder_questions))
+                .route("/{id}/questions/{question_id}", web::put().to(handlers::update_question))
+                .route("/{id}/questions/{question_id}", web::delete().to(handlers::delete_question))
+                .route("/{id}/questions/{question_id}/options", web::post().to(handlers::add_option))
+                .route("/{id}/questions/{question_id}/options/order", web::put().to(handlers::reorder_options))
+                .route("/{id}/questions/{question_id}/options/{option_id}", web::put().to(handlers::update_option))
+                .route("/{id}/questions/{question_id}/options/{option_id}", web::delete().to(handlers::delete_option))
+                .route("/{id}/solve", web::post().to(handlers::submit_answer))
+                .route("/{id}/questions/{question_id}/hints/next", web::get().to(handlers::next_hint))
+                .route("/{id}/attempts", web::post().to(handlers::start_attempt))
+                .route("/{id}/attempts/{attempt_id}", web::get().to(handlers::get_attempt))
+                .route("/{id}/attempts/{attempt_id}/questions", web::get().to(handlers::get_attempt_questions))
+                .route("/{id}/attempts/{attempt_id}/answers", web::post().to(handlers::submit_attempt_answer))
+                .route("/{id}/attempts/{attempt_id}/finish", web::post().to(handlers::finish_attempt))
+        )
+        .service(
+            web::scope("/questions")
+                .route("/random", web::get().to(handlers::get_random_question))
+        )
+        .service(
+            web::scope("/me")
+                .route("/progress", web::get().to(handlers::get_progress))
+                .route("/review", web::get().to(handlers::get_review_queue))
+                .route("/review", web::post().to(handlers::grade_review))
+                .route("/achievements", web::get().to(handlers::get_achievements))
+        )
+        .service(
+            web::scope("/practice/sessions")
+                .route("", web::post().to(handlers::start_practice))
+                .route("/{id}", web::get().to(handlers::get_practice))
+                .route("/{id}/next", web::get().to(handlers::next_practice_question))
+                .route("/{id}/answers", web::post().to(handlers::submit_practice_answer))
+        )
+        .service(
+            web::scope("/leaderboards")
+                .route("", web::get().to(handlers::get_leaderboard))
+                .route("/me", web::get().to(handlers::get_my_rank))
+        )
+        .service(
+            web::scope("/daily")
+                .route("", web::get().to(handlers::get_daily_challenge))
+                .route("/attempts", web::post().to(handlers::start_daily_attempt))
+                .route("/streak", web::get().to(handlers::get_daily_streak))
+        );
+}
+
+/// How often the background sweeper finalizes attempts that ran out of time.
+const EXPIRY_SWEEP_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Starts the background task that finalizes attempts whose time limit ran out; shared by
+/// the HTTP server and the Lambda entrypoint. Overdue attempts are also expired lazily on
+/// access, which covers the time a Lambda instance spends frozen between invocations.
+pub fn spawn_expiry_sweeper(data: web::Data<AppState>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(EXPIRY_SWEEP_INTERVAL);
+        loop {
+            interval.tick().await;
+            let expired = data.expire_overdue_attempts();
+            if expired > 0 {
+                log::info!("Expired {} overdue attempts", expired);
+            }
+        }
     });
+}
+
+pub fn run(listener: TcpListener, quizzes: Vec<Quiz>, categories: Vec<Category>) -> Result<Server, std::io::Error> {
+    let data = web::Data::new(AppState::new(quizzes, categories));
+    spawn_expiry_sweeper(data.clone());
 
     let server = HttpServer::new(move || {
         App::new()
             .app_data(data.clone())
             .wrap(middleware::Logger::default())
-            .service(
-                SwaggerUi::new("/swagger-ui/{_:.*}").url("/api-docs/openapi.json", ApiDoc::openapi())
-            )
-            .route("/health", web::get().to(handlers::health_check))
-            .service(
-                web::scope("/categories")
-                    .route("", web::post().to(handlers::create_category))
-                    .route("", web::get().to(handlers::list_categories))
-            )
-            .service(
-                web::scope("/quizzes")
-                    .route("", web::post().to(handlers::create_quiz))
-                    .route("", web::get().to(handlers::list_quizzes))
-                    .route("/random", web::get().to(handlers::get_random_quiz))
-                    .route("/{id}", web::get().to(handlers::get_quiz))
-                    .route("/{id}", web::put().to(handlers::update_quiz))
-                    .route("/{id}", web::delete().to(handlers::delete_quiz))
-                    .route("/{id}/solve", web::post().to(handlers::submit_answer))
-            )
+            .configure(configure)
     })
     .listen(listener)?
     .run();
@@ -1,6 +1,7 @@
 use coding_quiz_api::run;
-use coding_quiz_api::models::{Quiz, Question, QuestionOption};
+use coding_quiz_api::models::{Quiz, Question, QuestionOption, Difficulty, RevealPolicy};
 use coding_quiz_api::id::Id;
+use coding_quiz_api::validation;
 use std::net::TcpListener;
 use env_logger::Env;
 use walkdir::WalkDir;
@@ -10,6 +11,14 @@ use serde::Deserialize;
 struct QuizSeed {
     title: String,
     tags: Option<Vec<String>>,
+    difficulty: Option<Difficulty>,
+    /// Default per-question estimate for questions that don't set their own
+    estimated_time_seconds: Option<u32>,
+    reveal_policy: Option<RevealPolicy>,
+    time_limit_seconds: Option<u32>,
+    exam_mode: Option<bool>,
+    shuffle_options: Option<bool>,
+    shuffle_questions: Option<bool>,
     questions: Vec<QuestionSeed>,
 }
 
@@ -18,6 +27,10 @@ struct QuestionSeed {
     text: String,
     options: Vec<OptionSeed>,
     explanation: Option<String>,
+    difficulty: Option<Difficulty>,
+    estimated_time_seconds: Option<u32>,
+    time_limit_seconds: Option<u32>,
+    hints: Option<Vec<String>>,
 }
 
 #[derive(Deserialize)]
@@ -34,7 +47,7 @@ fn load_quizzes() -> Vec<Quiz> {
     log::info!("Loading quizzes from {}", seed_dir);
 
     for entry in WalkDir::new(seed_dir).into_iter().filter_map(|e| e.ok()) {
-        if entry.path().extension().map_or(false, |ext| ext == "json") {
+        if entry.path().extension().is_some_and(|ext| ext == "json") {
             let path = entry.path();
             log::info!("Loading file: {:?}", path);
             
@@ -56,6 +69,7 @@ fn load_quizzes() -> Vec<Quiz> {
 
             // Map to Domain Models with NEW IDs every boot
             let quiz_id = Id::new();
+            let default_time = seed.estimated_time_seconds;
             let questions: Vec<Question> = seed.questions.into_iter().map(|q| {
                 let q_id = Id::new();
                 let options = q.options.into_iter().map(|o| {
@@ -71,6 +85,10 @@ fn load_quizzes() -> Vec<Quiz> {
                     text: q.text,
                     options,
                     explanation: q.explanation,
+                    difficulty: q.difficulty,
+                    estimated_time_seconds: q.estimated_time_seconds.or(default_time),
+                    time_limit_seconds: q.time_limit_seconds,
+                    hints: q.hints.unwrap_or_default(),
                 }
             }).collect();
 
@@ -79,13 +97,26 @@ fn load_quizzes() -> Vec<Quiz> {
             // Actually metadata says "categories" or derived. Let's just create a category if needed or leave null.
             // Leaving category_id None for simplicity as seeds don't have it directly mapped to specific ID.
             
-            quizzes.push(Quiz {
+            let quiz = Quiz {
                 id: quiz_id,
                 title: seed.title,
                 category_id: None, 
                 questions,
                 tags,
-            });
+                difficulty: seed.difficulty,
+                reveal_policy: seed.reveal_policy.unwrap_or_default(),
+                time_limit_seconds: seed.time_limit_seconds,
+                exam_mode: seed.exam_mode.unwrap_or(false),
+                shuffle_options: seed.shuffle_options.unwrap_or(true),
+                shuffle_questions: seed.shuffle_questions.unwrap_or(false),
+                generated: false,
+            };
+            if let Err(errors) = validation::validate(&quiz) {
+                let fields: Vec<String> = errors.iter().map(|e| format!("{} {}", e.field, e.message)).collect();
+                log::error!("Skipping invalid quiz {:?}: {}", path, fields.join(", "));
+                continue;
+            }
+            quizzes.push(quiz);
         }
     }
     log::info!("Loaded {} quizzes", quizzes.len());
@@ -103,40 +134,16 @@ async fn main() -> std::io::Result<()> {
 
     if is_lambda {
         log::info!("Starting Lambda handler");
+        let data = actix_web::web::Data::new(coding_quiz_api::state::AppState::new(quizzes, Vec::new()));
+        coding_quiz_api::spawn_expiry_sweeper(data.clone());
         let app_factory = move || {
-            // Need to recreate app factory logic here or extract it
-             use actix_web::{web, App, middleware};
-             use utoipa_swagger_ui::SwaggerUi;
-             use utoipa::OpenApi;
-             let data = web::Data::new(coding_quiz_api::state::AppState {
-                quizzes: std::sync::RwLock::new(quizzes.clone()),
-                categories: std::sync::RwLock::new(Vec::new()), 
-            });
-
+            use actix_web::{App, middleware};
             App::new()
-                .app_data(data)
+                .app_data(data.clone())
                 .wrap(middleware::Logger::default())
-                .service(
-                    SwaggerUi::new("/swagger-ui/{_:.*}").url("/api-docs/openapi.json", coding_quiz_api::ApiDoc::openapi())
-                )
-                .route("/health", web::get().to(coding_quiz_api::handlers::health_check))
-                .service(
-                    web::scope("/categories")
-                        .route("", web::post().to(coding_quiz_api::handlers::create_category))
-                        .route("", web::get().to(coding_quiz_api::handlers::list_categories))
-                )
-                .service(
-                    web::scope("/quizzes")
-                        .route("", web::post().to(coding_quiz_api::handlers::create_quiz))
-                        .route("", web::get().to(coding_quiz_api::handlers::list_quizzes))
-                        .route("/random", web::get().to(coding_quiz_api::handlers::get_random_quiz))
-                        .route("/{id}", web::get().to(coding_quiz_api::handlers::get_quiz))
-                        .route("/{id}", web::put().to(coding_quiz_api::handlers::update_quiz))
-                        .route("/{id}", web::delete().to(coding_quiz_api::handlers::delete_quiz))
-                        .route("/{id}/solve", web::post().to(coding_quiz_api::handlers::submit_answer))
-                )
+                .configure(coding_quiz_api::configure)
         };
-        lambda_web::run_actix_on_lambda(app_factory).await.map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+        lambda_web::run_actix_on_lambda(app_factory).await.map_err(std::io::Error::other)?;
     } else {
         log::info!("Starting server at http://0.0.0.0:8080");
         log::info!("Swagger UI available at http://localhost:8080/swagger-ui/");
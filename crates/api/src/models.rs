@@ -1,7 +1,62 @@
 use crate::id::Id;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use utoipa::{ToSchema, IntoParams};
 
+/// Difficulty levels, ordered from easiest to hardest.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    Beginner,
+    Intermediate,
+    Advanced,
+}
+
+/// Request for an ad-hoc quiz drawn from the question pool.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct GenerateQuizRequest {
+    pub title: Option<String>,
+    /// Number of questions to draw; fewer are returned if the pool is smaller
+    pub count: usize,
+    /// Questions from quizzes with any of these tags
+    pub tags: Option<Vec<String>>,
+    pub category_id: Option<Id>,
+    pub difficulty: Option<Difficulty>,
+    /// Makes the draw reproducible for the same pool
+    pub seed: Option<u64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RandomQuestionResponse {
+    /// Quiz the question belongs to, for use with `/quizzes/{id}/solve`
+    pub quiz_id: Id,
+    pub question: Question,
+}
+
+/// When learners get to see which option was correct.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RevealPolicy {
+    /// Reveal the correct option and option feedback on every answer
+    #[default]
+    Always,
+    /// Give option feedback per answer, but reveal correct options only once the attempt is finished
+    AfterFinalAttempt,
+    /// Never reveal correct options or option feedback
+    Never,
+}
+
+impl RevealPolicy {
+    pub fn shows_option_feedback(self) -> bool {
+        self != RevealPolicy::Never
+    }
+
+    pub fn reveals_correct_on_answer(self) -> bool {
+        self == RevealPolicy::Always
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub struct Quiz {
     pub id: Id,
@@ -9,6 +64,48 @@ pub struct Quiz {
     pub category_id: Option<Id>,
     pub questions: Vec<Question>,
     pub tags: Vec<String>,
+    pub difficulty: Option<Difficulty>,
+    #[serde(default)]
+    pub reveal_policy: RevealPolicy,
+    /// Time allowed for a whole attempt
+    pub time_limit_seconds: Option<u32>,
+    /// Hide correctness and explanations until the attempt is finished
+    #[serde(default)]
+    pub exam_mode: bool,
+    /// Present options in a per-attempt random order; turn off for "all of the above" style questions
+    #[serde(default = "default_true")]
+    pub shuffle_options: bool,
+    /// Present questions in a per-attempt random order
+    #[serde(default)]
+    pub shuffle_questions: bool,
+    /// Assembled ad hoc from the question pool; hidden from listings
+    #[serde(default)]
+    pub generated: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Quiz {
+    /// Sum of the estimated times of all questions, if any question has one.
+    /// Saturates at `u32::MAX` rather than overflowing.
+    pub fn estimated_time_seconds(&self) -> Option<u32> {
+        self.questions.iter()
+            .filter_map(|q| q.estimated_time_seconds)
+            .reduce(|a, b| a.saturating_add(b))
+    }
+
+    /// Difficulty of a question, falling back to the quiz difficulty.
+    pub fn question_difficulty(&self, question: &Question) -> Option<Difficulty> {
+        question.difficulty.or(self.difficulty)
+    }
+
+    /// Whether the quiz keeps answers back from learners at some point, through exam
+    /// mode or a reveal policy stricter than `always`.
+    pub fn withholds_answers(&self) -> bool {
+        self.exam_mode || self.reveal_policy != RevealPolicy::Always
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
@@ -17,6 +114,13 @@ pub struct Question {
     pub text: String,
     pub options: Vec<QuestionOption>,
     pub explanation: Option<String>,
+    pub difficulty: Option<Difficulty>,
+    pub estimated_time_seconds: Option<u32>,
+    /// Time allowed to answer this question within an attempt
+    pub time_limit_seconds: Option<u32>,
+    /// Ordered hints, revealed one at a time via the hints endpoint.
+    #[serde(default, skip_serializing)]
+    pub hints: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
@@ -25,6 +129,9 @@ pub struct QuestionOption {
     pub text: String,
     #[serde(skip_serializing)] 
     pub is_correct: bool,
+    /// Feedback on choosing this option; often gives the answer away, so it is only
+    /// released as `option_feedback` when the reveal policy allows
+    #[serde(skip_serializing)]
     pub description: Option<String>,
 }
 
@@ -34,6 +141,12 @@ pub struct CreateQuizRequest {
     pub category_id: Option<Id>,
     pub questions: Vec<CreateQuestionRequest>,
     pub tags: Option<Vec<String>>,
+    pub difficulty: Option<Difficulty>,
+    pub reveal_policy: Option<RevealPolicy>,
+    pub time_limit_seconds: Option<u32>,
+    pub exam_mode: Option<bool>,
+    pub shuffle_options: Option<bool>,
+    pub shuffle_questions: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -41,6 +154,12 @@ pub struct UpdateQuizRequest {
     pub title: Option<String>,
     pub category_id: Option<Id>,
     pub tags: Option<Vec<String>>,
+    pub difficulty: Option<Difficulty>,
+    pub reveal_policy: Option<RevealPolicy>,
+    pub time_limit_seconds: Option<u32>,
+    pub exam_mode: Option<bool>,
+    pub shuffle_options: Option<bool>,
+    pub shuffle_questions: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -48,6 +167,10 @@ pub struct CreateQuestionRequest {
     pub text: String,
     pub options: Vec<CreateOptionRequest>,
     pub explanation: Option<String>,
+    pub difficulty: Option<Difficulty>,
+    pub estimated_time_seconds: Option<u32>,
+    pub time_limit_seconds: Option<u32>,
+    pub hints: Option<Vec<String>>,
 }
 
 #[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
@@ -57,6 +180,56 @@ pub struct CreateOptionRequest {
     pub description: Option<String>,
 }
 
+impl From<&CreateQuestionRequest> for Question {
+    fn from(req: &CreateQuestionRequest) -> Self {
+        Question {
+            id: Id::new(),
+            text: req.text.clone(),
+            options: req.options.iter().map(QuestionOption::from).collect(),
+            explanation: req.explanation.clone(),
+            difficulty: req.difficulty,
+            estimated_time_seconds: req.estimated_time_seconds,
+            time_limit_seconds: req.time_limit_seconds,
+            hints: req.hints.clone().unwrap_or_default(),
+        }
+    }
+}
+
+impl From<&CreateOptionRequest> for QuestionOption {
+    fn from(req: &CreateOptionRequest) -> Self {
+        QuestionOption {
+            id: Id::new(),
+            text: req.text.clone(),
+            is_correct: req.is_correct,
+            description: req.description.clone(),
+        }
+    }
+}
+
+/// Changes to a single question; options are managed through their own endpoints.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateQuestionRequest {
+    pub text: Option<String>,
+    pub explanation: Option<String>,
+    pub difficulty: Option<Difficulty>,
+    pub estimated_time_seconds: Option<u32>,
+    pub time_limit_seconds: Option<u32>,
+    pub hints: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateOptionRequest {
+    pub text: Option<String>,
+    pub is_correct: Option<bool>,
+    pub description: Option<String>,
+}
+
+/// New order for questions or options; must list every existing ID exactly once.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ReorderRequest {
+    pub ids: Vec<Id>,
+}
+
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct SubmitAnswerRequest {
     pub question_id: Id,
@@ -66,21 +239,470 @@ pub struct SubmitAnswerRequest {
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct AnswerResponse {
-    pub correct: bool,
+    /// Omitted for exam-mode quizzes until the attempt is finished
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub correct: Option<bool>,
     pub message: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub explanation: Option<String>,
+    /// Feedback attached to the chosen option
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub option_feedback: Option<String>,
+    /// Correct options, when the quiz reveal policy allows it and the answer was wrong
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub correct_options: Vec<RevealedOption>,
+    /// Points earned for this answer after hint penalties (0.0 - 1.0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<f64>,
+    pub hints_used: usize,
+}
+
+#[derive(Debug, Serialize, ToSchema, Clone)]
+pub struct RevealedOption {
+    pub id: Id,
+    pub text: String,
+}
+
+impl From<&QuestionOption> for RevealedOption {
+    fn from(option: &QuestionOption) -> Self {
+        RevealedOption { id: option.id, text: option.text.clone() }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct HintResponse {
+    pub hint: String,
+    /// 1-based position of this hint
+    pub hint_number: usize,
+    pub remaining: usize,
+    /// Total points that will be deducted from this question's score
+    pub penalty: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AttemptStatus {
+    InProgress,
+    Finished,
+    /// Finalized automatically because the time limit ran out
+    Expired,
+}
+
+/// A learner's run through a quiz, from start to finish.
+#[derive(Debug, Serialize, Clone, ToSchema)]
+pub struct Attempt {
+    pub id: Id,
+    pub quiz_id: Id,
+    pub user_email: Option<String>,
+    pub status: AttemptStatus,
+    pub started_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub answers: Vec<AttemptAnswer>,
+    /// Hints revealed per question during this attempt
+    #[schema(value_type = HashMap<String, usize>)]
+    pub hints_used: HashMap<Id, usize>,
+}
+
+impl Attempt {
+    pub fn new(quiz: &Quiz, user_email: Option<String>) -> Self {
+        let started_at = Utc::now();
+        Attempt {
+            id: Id::new(),
+            quiz_id: quiz.id,
+            user_email,
+            status: AttemptStatus::InProgress,
+            started_at,
+            expires_at: quiz.time_limit_seconds.map(|s| started_at + Duration::seconds(s.into())),
+            finished_at: None,
+            answers: Vec::new(),
+            hints_used: HashMap::new(),
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.status != AttemptStatus::InProgress
+    }
+
+    /// Finalizes the attempt if its time limit has run out; returns whether it expired now.
+    pub fn expire_if_overdue(&mut self, now: DateTime<Utc>) -> bool {
+        match self.expires_at {
+            Some(expires_at) if self.status == AttemptStatus::InProgress && now >= expires_at => {
+                self.status = AttemptStatus::Expired;
+                self.finished_at = Some(expires_at);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// When the clock for the next question started: the last answer, or the attempt start.
+    /// Questions are timed as if answered in sequence.
+    pub fn question_started_at(&self) -> DateTime<Utc> {
+        self.answers.iter().map(|a| a.answered_at).max().unwrap_or(self.started_at)
+    }
+
+    pub fn answer_for(&self, question_id: Id) -> Option<&AttemptAnswer> {
+        self.answers.iter().find(|a| a.question_id == question_id)
+    }
+
+    pub fn hints_for(&self, question_id: Id) -> usize {
+        self.hints_used.get(&question_id).copied().unwrap_or(0)
+    }
+}
+
+#[derive(Debug, Serialize, Clone, ToSchema)]
+pub struct AttemptAnswer {
+    pub question_id: Id,
+    pub option_id: Id,
+    #[serde(skip_serializing)]
+    pub correct: bool,
+    /// Submitted after the question's time limit; counts as incorrect
+    pub timed_out: bool,
+    pub answered_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, ToSchema, Default)]
+pub struct StartAttemptRequest {
+    pub user_email: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AttemptAnswerRequest {
+    pub question_id: Id,
+    pub option_id: Id,
+}
+
+/// Final outcome of an attempt.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AttemptResult {
+    pub attempt_id: Id,
+    pub quiz_id: Id,
+    /// Sum of per-question scores after hint penalties
+    pub score: f64,
+    pub max_score: f64,
+    pub correct_count: usize,
+    pub total_questions: usize,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub duration_seconds: i64,
+    pub breakdown: Vec<QuestionResult>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct QuestionResult {
+    pub question_id: Id,
+    pub answered: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub option_id: Option<Id>,
+    pub correct: bool,
+    pub timed_out: bool,
+    pub hints_used: usize,
+    pub score: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<String>,
+    /// Correct options, unless the quiz reveal policy is `never`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub correct_options: Vec<RevealedOption>,
+}
+
+/// One answer given by a learner, kept for progress tracking.
+#[derive(Debug, Serialize, Clone, ToSchema)]
+pub struct AnswerRecord {
+    pub quiz_id: Id,
+    pub question_id: Id,
+    pub option_id: Id,
+    pub correct: bool,
+    pub answered_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ProgressReport {
+    pub user_email: String,
+    pub total_answers: usize,
+    pub correct_answers: usize,
+    pub accuracy: f64,
+    pub questions_total: usize,
+    pub questions_seen: usize,
+    pub questions_never_attempted: usize,
+    pub by_tag: Vec<TopicProgress>,
+    pub by_category: Vec<TopicProgress>,
+}
+
+/// Progress within one tag or category.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TopicProgress {
+    /// Category ID; absent for tags
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Id>,
+    pub name: String,
+    pub answers: usize,
+    pub correct: usize,
+    pub accuracy: f64,
+    pub questions_total: usize,
+    pub questions_seen: usize,
+    pub questions_never_attempted: usize,
+}
+
+/// Spaced-repetition schedule of one question for one learner (SM-2).
+#[derive(Debug, Serialize, Clone, ToSchema)]
+pub struct ReviewCard {
+    pub question_id: Id,
+    pub quiz_id: Id,
+    pub ease_factor: f64,
+    pub interval_days: u32,
+    /// Consecutive successful reviews
+    pub repetitions: u32,
+    pub due_at: DateTime<Utc>,
+    pub last_reviewed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReviewItem {
+    pub card: ReviewCard,
+    pub question: Question,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReviewQueue {
+    /// Total number of due cards; `items` may be limited
+    pub due_count: usize,
+    pub items: Vec<ReviewItem>,
+    /// When the next card not yet due becomes due
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_due_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ReviewGradeRequest {
+    pub user_email: String,
+    pub question_id: Id,
+    /// SM-2 grade from 0 (blackout) to 5 (perfect recall)
+    pub grade: u8,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct StartPracticeRequest {
+    pub user_email: String,
+    /// Only draw questions from quizzes with any of these tags
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub category_id: Option<Id>,
+    /// Number of questions in the session (default 10, max 50)
+    pub length: Option<usize>,
+}
+
+/// Adaptive practice session; questions are served one at a time.
+#[derive(Debug, Serialize, Clone, ToSchema)]
+pub struct PracticeSession {
+    pub id: Id,
+    pub user_email: String,
+    pub tags: Vec<String>,
+    pub category_id: Option<Id>,
+    pub length: usize,
+    /// Questions served so far, in order
+    pub asked: Vec<Id>,
+    pub correct_count: usize,
+    pub started_at: DateTime<Utc>,
+    /// Served question awaiting an answer, as (quiz ID, question ID)
+    #[serde(skip)]
+    pub current: Option<(Id, Id)>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PracticeQuestion {
+    pub session_id: Id,
+    pub quiz_id: Id,
+    /// 1-based position within the session
+    pub number: usize,
+    pub question: Question,
+    /// Learner's current ability rating
+    pub ability: f64,
+    /// Calibrated difficulty rating of the question
+    pub question_rating: f64,
+    /// Estimated chance of answering correctly
+    pub expected_success: f64,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PracticeAnswerRequest {
+    pub question_id: Id,
+    pub option_id: Id,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PracticeAnswerResponse {
+    #[serde(flatten)]
+    pub answer: AnswerResponse,
+    /// Learner's ability rating after this answer
+    pub ability: f64,
+    /// Questions left in the session
+    pub remaining: usize,
+}
+
+/// Period of finished attempts a leaderboard covers (UTC).
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaderboardWindow {
+    Daily,
+    Weekly,
+    #[default]
+    AllTime,
+}
+
+#[derive(Debug, Serialize, Clone, ToSchema)]
+pub struct LeaderboardEntry {
+    /// 1-based; learners tied on score and duration share a rank
+    pub rank: usize,
+    /// Masked, e.g. `c***@example.com`
+    pub user_email: String,
+    /// Sum of the learner's best score per quiz
+    pub score: f64,
+    pub max_score: f64,
+    /// Number of quizzes counted
+    pub quizzes: usize,
+    /// Total duration of the counted attempts
+    pub duration_seconds: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Leaderboard {
+    pub window: LeaderboardWindow,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quiz_id: Option<Id>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    /// Number of ranked learners
+    pub total: usize,
+    pub page: usize,
+    pub per_page: usize,
+    pub entries: Vec<LeaderboardEntry>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DailyChallenge {
+    pub date: NaiveDate,
+    /// Questions and options come in the order of the learner's attempt, if started
+    pub quiz: Quiz,
+    /// The learner's attempt at this challenge, if started
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attempt: Option<Attempt>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct StartDailyRequest {
+    pub user_email: String,
+    /// UTC offset such as `+02:00` deciding which day it is; defaults to UTC
+    pub tz: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DailyStreak {
+    pub user_email: String,
+    /// Consecutive days completed up to today, or up to yesterday if today is still open
+    pub current: u32,
+    pub longest: u32,
+    pub completed_today: bool,
+    pub last_completed: Option<NaiveDate>,
+}
+
+#[derive(Debug, Serialize, Clone, ToSchema)]
+pub struct Badge {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub awarded_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AchievementInfo {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AchievementsReport {
+    pub user_email: String,
+    /// Badges in the order they were awarded
+    pub earned: Vec<Badge>,
+    /// Achievements not earned yet
+    pub locked: Vec<AchievementInfo>,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchHitKind {
+    Quiz,
+    Question,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SearchHighlight {
+    /// `title`, `question`, `option` or `explanation`
+    pub field: String,
+    /// HTML-escaped text with matches wrapped in `<mark>`
+    pub snippet: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SearchHit {
+    pub kind: SearchHitKind,
+    pub quiz_id: Id,
+    pub quiz_title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub question_id: Option<Id>,
+    pub score: f64,
+    pub highlights: Vec<SearchHighlight>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SearchResults {
+    pub query: String,
+    /// Number of matching documents; `results` may be limited
+    pub total: usize,
+    pub results: Vec<SearchHit>,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
 pub struct Category {
     pub id: Id,
     pub name: String,
+    /// Enclosing category; top-level categories have none
+    #[serde(default)]
+    pub parent_id: Option<Id>,
+}
+
+/// A category with its subcategories, as returned by `GET /categories/tree`.
+#[derive(Debug, Serialize, ToSchema, Clone)]
+pub struct CategoryNode {
+    pub id: Id,
+    pub name: String,
+    pub children: Vec<CategoryNode>,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateCategoryRequest {
     pub name: String,
+    pub parent_id: Option<Id>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateCategoryRequest {
+    pub name: Option<String>,
+    /// Move under another category; `null` makes it top-level, leaving it out keeps the parent
+    #[serde(default, deserialize_with = "present")]
+    #[schema(value_type = Option<Id>)]
+    pub parent_id: Option<Option<Id>>,
+}
+
+/// Tells an explicit `null` (`Some(None)`) apart from a missing field (`None`).
+fn present<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
@@ -89,10 +711,123 @@ pub struct Tag {
     pub name: String,
 }
 
+/// How `GET /quizzes` combines several `tags`.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TagMode {
+    /// Quizzes carrying at least one of the tags
+    #[default]
+    Any,
+    /// Quizzes carrying every tag
+    All,
+}
+
+/// How often a tag is used across listed quizzes.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct TagUsage {
+    pub id: Id,
+    pub name: String,
+    pub quiz_count: usize,
+    pub question_count: usize,
+}
+
+/// Sort keys accepted by `GET /quizzes`.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QuizSort {
+    Difficulty,
+    EstimatedTime,
+    /// Alphabetical, ignoring case
+    Title,
+    /// Creation time
+    Created,
+    QuestionCount,
+}
+
+/// Shape of the quizzes returned by `GET /quizzes`.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum QuizView {
+    /// Complete quizzes with questions and options
+    #[default]
+    Full,
+    /// `QuizSummary` without questions
+    Summary,
+}
+
+/// Lightweight quiz for listings.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
+pub struct QuizSummary {
+    pub id: Id,
+    pub title: String,
+    pub category_id: Option<Id>,
+    pub tags: Vec<String>,
+    pub difficulty: Option<Difficulty>,
+    pub question_count: usize,
+}
+
+impl From<&Quiz> for QuizSummary {
+    fn from(quiz: &Quiz) -> Self {
+        QuizSummary {
+            id: quiz.id,
+            title: quiz.title.clone(),
+            category_id: quiz.category_id,
+            tags: quiz.tags.clone(),
+            difficulty: quiz.difficulty,
+            question_count: quiz.questions.len(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
 #[derive(Debug, Deserialize, ToSchema, IntoParams)]
 pub struct PaginationParams {
+    /// 1-based page number (default 1)
     pub page: Option<u32>,
+    /// Items per page (default 10, max 100)
     pub per_page: Option<u32>,
+    /// Continue after this ID instead of using `page`; taken from `next_cursor`
+    pub cursor: Option<Id>,
+}
+
+/// One page of a listing.
+#[derive(Debug, Serialize, ToSchema)]
+#[aliases(QuizPage = Page<Quiz>, QuizSummaryPage = Page<QuizSummary>, CategoryPage = Page<Category>)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Number of items across all pages
+    pub total: usize,
+    /// Current page; absent for cursor pages
+    pub page: Option<u32>,
+    pub per_page: u32,
+    /// Link to the following page
+    pub next: Option<String>,
+    /// Link to the preceding page; cursor pages only link forward
+    pub prev: Option<String>,
+    /// Cursor for the following page, when the listing is in creation order
+    pub next_cursor: Option<Id>,
+}
+
+impl<T> Page<T> {
+    /// Converts the items, keeping the paging details.
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            total: self.total,
+            page: self.page,
+            per_page: self.per_page,
+            next: self.next,
+            prev: self.prev,
+            next_cursor: self.next_cursor,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -100,3 +835,18 @@ pub struct ErrorResponse {
     pub error: String,
 }
 
+/// A failed validation rule.
+#[derive(Debug, Serialize, ToSchema, PartialEq)]
+pub struct FieldError {
+    /// Path to the field, e.g. `questions[0].options[1].text`
+    pub field: String,
+    pub message: String,
+}
+
+/// Body of a 422 response for a request that breaks field rules.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ValidationErrorResponse {
+    pub error: String,
+    pub fields: Vec<FieldError>,
+}
+
@@ -0,0 +1,165 @@
+use crate::id::Id;
+use crate::models::{Page, PaginationParams};
+
+pub const DEFAULT_PER_PAGE: u32 = 10;
+pub const MAX_PER_PAGE: u32 = 100;
+
+/// Builds next/prev links from the request path and its query string, replacing only the paging parameters.
+pub struct PageLinks {
+    path: String,
+    /// Query pairs other than `page`, `per_page` and `cursor`, kept as sent
+    query: Vec<String>,
+}
+
+impl PageLinks {
+    pub fn new(path: &str, query: &str) -> Self {
+        let query = query.split('&')
+            .filter(|pair| !pair.is_empty())
+            .filter(|pair| {
+                let key = pair.split('=').next().unwrap_or_default();
+                !matches!(key, "page" | "per_page" | "cursor")
+            })
+            .map(String::from)
+            .collect();
+        PageLinks { path: path.to_string(), query }
+    }
+
+    fn link(&self, paging: String) -> String {
+        let mut query = self.query.clone();
+        query.push(paging);
+        format!("{}?{}", self.path, query.join("&"))
+    }
+}
+
+/// Cuts one page out of `items`.
+///
+/// With `cursor` set, the page holds the items after that ID, so `items` must be in ID order
+/// and `id_of` given; this keeps pages stable while quizzes are added. Otherwise `page`
+/// (1-based) selects the page. `id_of` also enables `next_cursor` on either kind of page.
+pub fn paginate<T>(
+    items: Vec<T>,
+    params: &PaginationParams,
+    id_of: Option<fn(&T) -> Id>,
+    links: &PageLinks,
+) -> Result<Page<T>, String> {
+    if params.page == Some(0) {
+        return Err("page starts at 1".to_string());
+    }
+    if params.page.is_some() && params.cursor.is_some() {
+        return Err("Use either page or cursor, not both".to_string());
+    }
+    let per_page = params.per_page.unwrap_or(DEFAULT_PER_PAGE).clamp(1, MAX_PER_PAGE);
+    let total = items.len();
+
+    let (page, start) = match params.cursor {
+        Some(cursor) => {
+            let id_of = id_of.ok_or("cursor cannot be combined with sort")?;
+            let start = items.iter().position(|item| id_of(item).to_i64() > cursor.to_i64()).unwrap_or(total);
+            (None, start)
+        }
+        None => {
+            let page = params.page.unwrap_or(1);
+            let start = ((page - 1) as usize).saturating_mul(per_page as usize).min(total);
+            (Some(page), start)
+        }
+    };
+
+    let items: Vec<T> = items.into_iter().skip(start).take(per_page as usize).collect();
+    let has_more = start + items.len() < total;
+    let next_cursor = match (id_of, items.last()) {
+        (Some(id_of), Some(last)) if has_more => Some(id_of(last)),
+        _ => None,
+    };
+    let next = match (page, next_cursor) {
+        (Some(page), _) if has_more => Some(links.link(format!("page={}&per_page={}", page + 1, per_page))),
+        (None, Some(cursor)) => Some(links.link(format!("cursor={}&per_page={}", cursor, per_page))),
+        _ => None,
+    };
+    let prev = page
+        .filter(|page| *page > 1)
+        .map(|page| links.link(format!("page={}&per_page={}", page - 1, per_page)));
+
+    Ok(Page { items, total, page, per_page, next, prev, next_cursor })
+}
+
+/// Formats the `Link` header for a page, if it has neighbours.
+pub fn link_header<T>(page: &Page<T>) -> Option<String> {
+    let links: Vec<String> = [(&page.next, "next"), (&page.prev, "prev")]
+        .into_iter()
+        .filter_map(|(url, rel)| url.as_ref().map(|url| format!("<{}>; rel=\"{}\"", url, rel)))
+        .collect();
+    (!links.is_empty()).then(|| links.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(page: Option<u32>, per_page: Option<u32>, cursor: Option<Id>) -> PaginationParams {
+        PaginationParams { page, per_page, cursor }
+    }
+
+    fn ids(count: usize) -> Vec<Id> {
+        (0..count).map(|_| Id::new()).collect()
+    }
+
+    fn id_of(id: &Id) -> Id {
+        *id
+    }
+
+    #[test]
+    fn pages_by_offset_with_links() {
+        let links = PageLinks::new("/quizzes", "tags=rust&page=2&per_page=2");
+        let page = paginate((1..=5).collect(), &params(Some(2), Some(2), None), None, &links).unwrap();
+        assert_eq!(page.items, vec![3, 4]);
+        assert_eq!(page.total, 5);
+        assert_eq!(page.next.as_deref(), Some("/quizzes?tags=rust&page=3&per_page=2"));
+        assert_eq!(page.prev.as_deref(), Some("/quizzes?tags=rust&page=1&per_page=2"));
+        assert_eq!(
+            link_header(&page).as_deref(),
+            Some("</quizzes?tags=rust&page=3&per_page=2>; rel=\"next\", </quizzes?tags=rust&page=1&per_page=2>; rel=\"prev\"")
+        );
+
+        let last = paginate((1..=5).collect(), &params(Some(3), Some(2), None), None, &links).unwrap();
+        assert_eq!(last.items, vec![5]);
+        assert_eq!(last.next, None);
+    }
+
+    #[test]
+    fn rejects_page_zero_and_clamps_per_page() {
+        let links = PageLinks::new("/quizzes", "");
+        assert!(paginate(vec![1], &params(Some(0), None, None), None, &links).is_err());
+
+        let page = paginate((0..300).collect(), &params(None, Some(1000), None), None, &links).unwrap();
+        assert_eq!(page.per_page, MAX_PER_PAGE);
+        assert_eq!(page.items.len(), 100);
+        let page = paginate((0..3).collect(), &params(None, Some(0), None), None, &links).unwrap();
+        assert_eq!(page.items, vec![0]);
+
+        let beyond = paginate((0..3).collect(), &params(Some(u32::MAX), None, None), None, &links).unwrap();
+        assert!(beyond.items.is_empty());
+        assert_eq!(beyond.next, None);
+    }
+
+    #[test]
+    fn pages_by_cursor() {
+        let links = PageLinks::new("/quizzes", "cursor=X");
+        let all = ids(5);
+
+        let first = paginate(all.clone(), &params(None, Some(2), None), Some(id_of), &links).unwrap();
+        assert_eq!(first.next_cursor, Some(all[1]));
+
+        let second = paginate(all.clone(), &params(None, Some(2), first.next_cursor), Some(id_of), &links).unwrap();
+        assert_eq!(second.items, all[2..4]);
+        assert_eq!(second.page, None);
+        assert_eq!(second.prev, None);
+        assert_eq!(second.next, Some(format!("/quizzes?cursor={}&per_page=2", all[3])));
+
+        // A cursor whose item was deleted still resumes after it
+        let remaining: Vec<Id> = all.iter().copied().filter(|id| *id != all[1]).collect();
+        let resumed = paginate(remaining, &params(None, Some(2), Some(all[1])), Some(id_of), &links).unwrap();
+        assert_eq!(resumed.items, all[2..4]);
+
+        assert!(paginate(all, &params(None, None, Some(Id::new())), None, &links).is_err());
+    }
+}
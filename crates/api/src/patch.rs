@@ -0,0 +1,344 @@
+use std::collections::HashSet;
+use serde_json::{Map, Value};
+use crate::id::Id;
+use crate::models::Quiz;
+
+pub const MERGE_PATCH: &str = "application/merge-patch+json";
+pub const JSON_PATCH: &str = "application/json-patch+json";
+
+#[derive(Debug, PartialEq)]
+pub enum PatchError {
+    /// The patch document itself is malformed
+    Invalid(String),
+    /// An operation targets a location that doesn't exist
+    Unprocessable(String),
+    /// A `test` operation did not match
+    TestFailed(String),
+}
+
+/// Applies an RFC 7396 merge patch: objects merge recursively, `null` removes a member,
+/// anything else replaces the target.
+pub fn merge_patch(target: &mut Value, patch: &Value) {
+    let Value::Object(members) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = Value::Object(Map::new());
+    }
+    let Value::Object(target) = target else { return };
+    for (key, value) in members {
+        if value.is_null() {
+            target.remove(key);
+        } else {
+            merge_patch(target.entry(key.clone()).or_insert(Value::Null), value);
+        }
+    }
+}
+
+/// Applies an RFC 6902 JSON Patch. Operations apply in order and any failure leaves
+/// `target` untouched.
+pub fn json_patch(target: &mut Value, patch: &Value) -> Result<(), PatchError> {
+    let Value::Array(operations) = patch else {
+        return Err(PatchError::Invalid("A JSON Patch must be an array of operations".to_string()));
+    };
+    let mut patched = target.clone();
+    for operation in operations {
+        apply(&mut patched, operation)?;
+    }
+    *target = patched;
+    Ok(())
+}
+
+fn apply(target: &mut Value, operation: &Value) -> Result<(), PatchError> {
+    let field = |name: &str| -> Result<&str, PatchError> {
+        operation.get(name)
+            .and_then(Value::as_str)
+            .ok_or_else(|| PatchError::Invalid(format!("Operation is missing '{}'", name)))
+    };
+    let value = || operation.get("value")
+        .cloned()
+        .ok_or_else(|| PatchError::Invalid("Operation is missing 'value'".to_string()));
+
+    let path = parse_pointer(field("path")?)?;
+    match field("op")? {
+        "add" => add(target, &path, value()?),
+        "remove" => remove(target, &path).map(drop),
+        "replace" => {
+            *resolve(target, &path)? = value()?;
+            Ok(())
+        }
+        "move" => {
+            let from = parse_pointer(field("from")?)?;
+            if path.len() > from.len() && path[..from.len()] == from[..] {
+                return Err(PatchError::Invalid("Cannot move a value into itself".to_string()));
+            }
+            let moved = remove(target, &from)?;
+            add(target, &path, moved)
+        }
+        "copy" => {
+            let copied = resolve(target, &parse_pointer(field("from")?)?)?.clone();
+            add(target, &path, copied)
+        }
+        "test" => {
+            if *resolve(target, &path)? == value()? {
+                Ok(())
+            } else {
+                Err(PatchError::TestFailed(format!("Test failed at '{}'", field("path")?)))
+            }
+        }
+        op => Err(PatchError::Invalid(format!("Unknown operation '{}'", op))),
+    }
+}
+
+/// Splits a JSON Pointer into unescaped reference tokens.
+fn parse_pointer(pointer: &str) -> Result<Vec<String>, PatchError> {
+    if pointer.is_empty() {
+        return Ok(Vec::new());
+    }
+    let Some(rest) = pointer.strip_prefix('/') else {
+        return Err(PatchError::Invalid(format!("Invalid JSON Pointer '{}'", pointer)));
+    };
+    Ok(rest.split('/').map(|token| token.replace("~1", "/").replace("~0", "~")).collect())
+}
+
+fn array_index(token: &str, len: usize) -> Result<usize, PatchError> {
+    let valid = !token.is_empty() && token.chars().all(|c| c.is_ascii_digit()) && (token == "0" || !token.starts_with('0'));
+    match token.parse::<usize>() {
+        Ok(index) if valid && index < len => Ok(index),
+        _ => Err(PatchError::Unprocessable(format!("Invalid array index '{}'", token))),
+    }
+}
+
+fn resolve<'a>(target: &'a mut Value, path: &[String]) -> Result<&'a mut Value, PatchError> {
+    path.iter().try_fold(target, |value, token| match value {
+        Value::Object(map) => map.get_mut(token)
+            .ok_or_else(|| PatchError::Unprocessable(format!("No member '{}'", token))),
+        Value::Array(items) => {
+            let index = array_index(token, items.len())?;
+            Ok(&mut items[index])
+        }
+        _ => Err(PatchError::Unprocessable(format!("Cannot descend into '{}'", token))),
+    })
+}
+
+fn add(target: &mut Value, path: &[String], value: Value) -> Result<(), PatchError> {
+    let Some((last, parent)) = path.split_last() else {
+        *target = value;
+        return Ok(());
+    };
+    match resolve(target, parent)? {
+        Value::Object(map) => {
+            map.insert(last.clone(), value);
+            Ok(())
+        }
+        Value::Array(items) => {
+            let index = if last == "-" { items.len() } else { array_index(last, items.len() + 1)? };
+            items.insert(index, value);
+            Ok(())
+        }
+        _ => Err(PatchError::Unprocessable(format!("Cannot add '{}' to a scalar", last))),
+    }
+}
+
+fn remove(target: &mut Value, path: &[String]) -> Result<Value, PatchError> {
+    let Some((last, parent)) = path.split_last() else {
+        return Err(PatchError::Unprocessable("Cannot remove the whole document".to_string()));
+    };
+    match resolve(target, parent)? {
+        Value::Object(map) => map.remove(last)
+            .ok_or_else(|| PatchError::Unprocessable(format!("No member '{}'", last))),
+        Value::Array(items) => {
+            let index = array_index(last, items.len())?;
+            Ok(items.remove(index))
+        }
+        _ => Err(PatchError::Unprocessable(format!("Cannot remove '{}' from a scalar", last))),
+    }
+}
+
+/// The quiz as patches see it: the public JSON plus the answer key (`is_correct` and option `description`) and `hints`,
+/// which responses leave out. `generated` is not part of the document and can't be patched in.
+pub fn quiz_document(quiz: &Quiz) -> Value {
+    let mut document = serde_json::to_value(quiz).unwrap_or_default();
+    if let Some(questions) = document.get_mut("questions").and_then(Value::as_array_mut) {
+        for (json, question) in questions.iter_mut().zip(&quiz.questions) {
+            json["hints"] = serde_json::json!(question.hints);
+            if let Some(options) = json.get_mut("options").and_then(Value::as_array_mut) {
+                for (json, option) in options.iter_mut().zip(&question.options) {
+                    json["is_correct"] = Value::Bool(option.is_correct);
+                    json["description"] = serde_json::json!(option.description);
+                }
+            }
+        }
+    }
+    if let Value::Object(map) = &mut document {
+        map.remove("generated");
+    }
+    document
+}
+
+/// Reads a patched document of `original` back into a quiz. Questions and options added
+/// without an `id` get a new one. Questions and options that keep their `id` but leave out
+/// `hints` or `description`, as when a merge patch replaces the whole `questions` array,
+/// keep the ones they had.
+pub fn quiz_from_document(mut document: Value, original: &Quiz) -> Result<Quiz, String> {
+    if document.get("generated").is_some() {
+        return Err("generated cannot be patched".to_string());
+    }
+    if let Some(questions) = document.get_mut("questions").and_then(Value::as_array_mut) {
+        for question in questions.iter_mut() {
+            assign_id(question);
+            let known = id_of(question).and_then(|id| original.questions.iter().find(|q| q.id == id));
+            if let (Some(known), Value::Object(map)) = (known, &mut *question) {
+                map.entry("hints").or_insert_with(|| serde_json::json!(known.hints));
+            }
+            if let Some(options) = question.get_mut("options").and_then(Value::as_array_mut) {
+                for option in options.iter_mut() {
+                    assign_id(option);
+                    let known = id_of(option).and_then(|id| known?.options.iter().find(|o| o.id == id));
+                    if let (Some(known), Value::Object(map)) = (known, option) {
+                        map.entry("description").or_insert_with(|| serde_json::json!(known.description));
+                    }
+                }
+            }
+        }
+    }
+    let mut quiz: Quiz = serde_json::from_value(document).map_err(|e| e.to_string())?;
+    quiz.generated = original.generated;
+    Ok(quiz)
+}
+
+fn id_of(item: &Value) -> Option<Id> {
+    serde_json::from_value(item.get("id")?.clone()).ok()
+}
+
+/// Checks the IDs of a patched quiz before it replaces `original` among `quizzes`;
+/// field rules are left to [`crate::validation`].
+pub fn validate(quizzes: &[Quiz], original: &Quiz, patched: &Quiz) -> Result<(), String> {
+    if patched.id != original.id {
+        return Err("id cannot be changed".to_string());
+    }
+    let mut question_ids = HashSet::new();
+    for question in &patched.questions {
+        if !question_ids.insert(question.id) {
+            return Err(format!("Duplicate question id {}", question.id));
+        }
+        // Question IDs identify answers, reviews and ratings, so they can't be borrowed from another quiz
+        let is_new = !original.questions.iter().any(|q| q.id == question.id);
+        if is_new && quizzes.iter().any(|quiz| quiz.questions.iter().any(|q| q.id == question.id)) {
+            return Err(format!("Question id {} belongs to another quiz", question.id));
+        }
+        let mut option_ids = HashSet::new();
+        if let Some(option) = question.options.iter().find(|o| !option_ids.insert(o.id)) {
+            return Err(format!("Duplicate option id {}", option.id));
+        }
+    }
+    Ok(())
+}
+
+fn assign_id(item: &mut Value) {
+    if let Value::Object(map) = item
+        && map.get("id").is_none_or(Value::is_null)
+    {
+        map.insert("id".to_string(), serde_json::json!(Id::new()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn merge_patch_follows_rfc_7396() {
+        let mut target = json!({ "title": "Goodbye!", "author": { "givenName": "John", "familyName": "Doe" }, "tags": ["example", "sample"] });
+        merge_patch(&mut target, &json!({ "title": "Hello!", "phoneNumber": "+01-123-456-7890", "author": { "familyName": null }, "tags": ["example"] }));
+        assert_eq!(target, json!({ "title": "Hello!", "author": { "givenName": "John" }, "tags": ["example"], "phoneNumber": "+01-123-456-7890" }));
+
+        let mut target = json!({ "a": "b" });
+        merge_patch(&mut target, &json!({ "a": { "bb": { "ccc": null } } }));
+        assert_eq!(target, json!({ "a": { "bb": {} } }));
+    }
+
+    #[test]
+    fn json_patch_applies_every_operation() {
+        let mut target = json!({ "foo": ["bar", "baz"], "qux": { "a/b": 1, "m~n": 2 } });
+        json_patch(&mut target, &json!([
+            { "op": "add", "path": "/foo/1", "value": "qux" },
+            { "op": "add", "path": "/foo/-", "value": "end" },
+            { "op": "remove", "path": "/qux/a~1b" },
+            { "op": "replace", "path": "/qux/m~0n", "value": 3 },
+            { "op": "copy", "from": "/foo/0", "path": "/first" },
+            { "op": "move", "from": "/foo/3", "path": "/last" },
+            { "op": "test", "path": "/foo", "value": ["bar", "qux", "baz"] }
+        ])).unwrap();
+        assert_eq!(target, json!({ "foo": ["bar", "qux", "baz"], "qux": { "m~n": 3 }, "first": "bar", "last": "end" }));
+    }
+
+    #[test]
+    fn json_patch_is_all_or_nothing() {
+        let original = json!({ "a": 1, "list": [1] });
+        let mut target = original.clone();
+
+        let failed = json_patch(&mut target, &json!([
+            { "op": "replace", "path": "/a", "value": 2 },
+            { "op": "test", "path": "/a", "value": 1 }
+        ]));
+        assert!(matches!(failed, Err(PatchError::TestFailed(_))));
+        assert_eq!(target, original);
+
+        assert!(matches!(json_patch(&mut target, &json!([{ "op": "remove", "path": "/missing" }])), Err(PatchError::Unprocessable(_))));
+        assert!(matches!(json_patch(&mut target, &json!([{ "op": "add", "path": "/list/01", "value": 2 }])), Err(PatchError::Unprocessable(_))));
+        assert!(matches!(json_patch(&mut target, &json!([{ "op": "jump", "path": "/a" }])), Err(PatchError::Invalid(_))));
+        assert!(matches!(json_patch(&mut target, &json!({ "op": "add" })), Err(PatchError::Invalid(_))));
+        assert!(matches!(json_patch(&mut target, &json!([{ "op": "move", "from": "/list", "path": "/list/0" }])), Err(PatchError::Invalid(_))));
+    }
+
+    #[test]
+    fn quiz_document_round_trips_the_answer_key() {
+        let quiz: Quiz = serde_json::from_value(json!({
+            "id": Id::new(), "title": "Q", "category_id": null, "tags": [], "difficulty": null, "time_limit_seconds": null,
+            "questions": [{
+                "id": Id::new(), "text": "?", "explanation": null, "difficulty": null,
+                "estimated_time_seconds": null, "time_limit_seconds": null, "hints": ["think"],
+                "options": [{ "id": Id::new(), "text": "yes", "is_correct": true, "description": null }]
+            }]
+        })).unwrap();
+
+        let mut document = quiz_document(&quiz);
+        assert_eq!(document["questions"][0]["options"][0]["is_correct"], true);
+        document["questions"][0]["options"].as_array_mut().unwrap().push(json!({ "text": "no", "is_correct": false }));
+
+        let patched = quiz_from_document(document, &quiz).unwrap();
+        assert_eq!(patched.questions[0].hints, ["think"]);
+        assert!(patched.questions[0].options[0].is_correct);
+        assert_eq!(patched.questions[0].options[0].id, quiz.questions[0].options[0].id);
+        assert!(!patched.questions[0].options[1].is_correct);
+    }
+
+    #[test]
+    fn quiz_from_document_keeps_hidden_fields_and_generated() {
+        let quiz: Quiz = serde_json::from_value(json!({
+            "id": Id::new(), "title": "Q", "category_id": null, "tags": [], "difficulty": null, "time_limit_seconds": null,
+            "questions": [{
+                "id": Id::new(), "text": "?", "explanation": null, "difficulty": null,
+                "estimated_time_seconds": null, "time_limit_seconds": null, "hints": ["think"],
+                "options": [{ "id": Id::new(), "text": "yes", "is_correct": true, "description": "Right." }]
+            }]
+        })).unwrap();
+
+        // A merge patch replacing `questions` with the public view of them
+        let mut document = quiz_document(&quiz);
+        merge_patch(&mut document, &json!({ "questions": [{
+            "id": quiz.questions[0].id, "text": "Renamed?",
+            "options": [{ "id": quiz.questions[0].options[0].id, "text": "yes", "is_correct": true }]
+        }] }));
+        let patched = quiz_from_document(document, &quiz).unwrap();
+        assert_eq!(patched.questions[0].hints, ["think"]);
+        assert_eq!(patched.questions[0].options[0].description.as_deref(), Some("Right."));
+
+        let mut document = quiz_document(&quiz);
+        merge_patch(&mut document, &json!({ "generated": true }));
+        assert!(quiz_from_document(document, &quiz).is_err());
+    }
+}
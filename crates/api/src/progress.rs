@@ -0,0 +1,94 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use crate::id::Id;
+use crate::models::{AnswerRecord, Category, ProgressReport, Quiz, TopicProgress};
+
+#[derive(Default)]
+struct Tally {
+    answers: usize,
+    correct: usize,
+    questions: HashSet<Id>,
+}
+
+impl Tally {
+    fn into_progress(self, id: Option<Id>, name: String, seen: &HashSet<Id>) -> TopicProgress {
+        let questions_seen = self.questions.iter().filter(|q| seen.contains(q)).count();
+        TopicProgress {
+            id,
+            name,
+            answers: self.answers,
+            correct: self.correct,
+            accuracy: accuracy(self.correct, self.answers),
+            questions_total: self.questions.len(),
+            questions_seen,
+            questions_never_attempted: self.questions.len() - questions_seen,
+        }
+    }
+}
+
+fn accuracy(correct: usize, answers: usize) -> f64 {
+    if answers == 0 { 0.0 } else { correct as f64 / answers as f64 }
+}
+
+/// Summarizes a learner's answer history against the current quiz catalogue.
+pub fn build_report(user_email: &str, records: &[AnswerRecord], quizzes: &[Quiz], categories: &[Category]) -> ProgressReport {
+    let seen: HashSet<Id> = records.iter().map(|r| r.question_id).collect();
+    // Generated quizzes reuse questions, so answers are attributed to the question's original quiz
+    let catalogue: Vec<&Quiz> = quizzes.iter().filter(|q| !q.generated).collect();
+    let mut owners: HashMap<Id, &Quiz> = HashMap::new();
+    for quiz in &catalogue {
+        for question in &quiz.questions {
+            owners.entry(question.id).or_insert(quiz);
+        }
+    }
+
+    let mut tags: BTreeMap<String, Tally> = BTreeMap::new();
+    let mut by_category: HashMap<Id, Tally> = HashMap::new();
+
+    // Question totals per topic come from the catalogue...
+    for quiz in &catalogue {
+        let question_ids = quiz.questions.iter().map(|q| q.id);
+        for tag in &quiz.tags {
+            tags.entry(tag.clone()).or_default().questions.extend(question_ids.clone());
+        }
+        if let Some(category_id) = quiz.category_id {
+            by_category.entry(category_id).or_default().questions.extend(question_ids);
+        }
+    }
+
+    // ...and answer counts from the history
+    for record in records {
+        let Some(quiz) = owners.get(&record.question_id) else { continue };
+        for tag in &quiz.tags {
+            let tally = tags.entry(tag.clone()).or_default();
+            tally.answers += 1;
+            tally.correct += usize::from(record.correct);
+        }
+        if let Some(category_id) = quiz.category_id {
+            let tally = by_category.entry(category_id).or_default();
+            tally.answers += 1;
+            tally.correct += usize::from(record.correct);
+        }
+    }
+
+    let questions_total = owners.len();
+    let questions_seen = owners.keys().filter(|id| seen.contains(id)).count();
+    let correct_answers = records.iter().filter(|r| r.correct).count();
+
+    let mut by_category: Vec<TopicProgress> = by_category.into_iter().map(|(id, tally)| {
+        let name = categories.iter().find(|c| c.id == id).map(|c| c.name.clone()).unwrap_or_default();
+        tally.into_progress(Some(id), name, &seen)
+    }).collect();
+    by_category.sort_by(|a, b| a.name.cmp(&b.name));
+
+    ProgressReport {
+        user_email: user_email.to_string(),
+        total_answers: records.len(),
+        correct_answers,
+        accuracy: accuracy(correct_answers, records.len()),
+        questions_total,
+        questions_seen,
+        questions_never_attempted: questions_total - questions_seen,
+        by_tag: tags.into_iter().map(|(name, tally)| tally.into_progress(None, name, &seen)).collect(),
+        by_category,
+    }
+}
@@ -0,0 +1,65 @@
+use chrono::Utc;
+use crate::models::{Attempt, AttemptResult, QuestionResult, Quiz, RevealPolicy, RevealedOption};
+
+/// Points deducted from a question's score for each revealed hint.
+pub const HINT_PENALTY: f64 = 0.25;
+
+/// Score for a single answered question, between 0.0 and 1.0.
+pub fn question_score(correct: bool, hints_used: usize) -> f64 {
+    if !correct {
+        return 0.0;
+    }
+    (1.0 - HINT_PENALTY * hints_used as f64).max(0.0)
+}
+
+/// Scores an attempt against its quiz, one point per question.
+pub fn score_attempt(quiz: &Quiz, attempt: &Attempt) -> AttemptResult {
+    let reveal = quiz.reveal_policy != RevealPolicy::Never;
+    let breakdown: Vec<QuestionResult> = quiz.questions.iter().map(|question| {
+        let answer = attempt.answer_for(question.id);
+        let correct = answer.is_some_and(|a| a.correct);
+        let hints_used = attempt.hints_for(question.id);
+        QuestionResult {
+            question_id: question.id,
+            answered: answer.is_some(),
+            option_id: answer.map(|a| a.option_id),
+            correct,
+            timed_out: answer.is_some_and(|a| a.timed_out),
+            hints_used,
+            score: question_score(correct, hints_used),
+            explanation: question.explanation.clone(),
+            correct_options: if reveal {
+                question.options.iter().filter(|o| o.is_correct).map(RevealedOption::from).collect()
+            } else {
+                Vec::new()
+            },
+        }
+    }).collect();
+
+    let finished_at = attempt.finished_at.unwrap_or_else(Utc::now);
+    AttemptResult {
+        attempt_id: attempt.id,
+        quiz_id: quiz.id,
+        score: breakdown.iter().map(|r| r.score).sum(),
+        max_score: breakdown.len() as f64,
+        correct_count: breakdown.iter().filter(|r| r.correct).count(),
+        total_questions: breakdown.len(),
+        started_at: attempt.started_at,
+        finished_at,
+        duration_seconds: (finished_at - attempt.started_at).num_seconds(),
+        breakdown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hints_reduce_score_down_to_zero() {
+        assert_eq!(question_score(true, 0), 1.0);
+        assert_eq!(question_score(true, 2), 0.5);
+        assert_eq!(question_score(true, 10), 0.0);
+        assert_eq!(question_score(false, 0), 0.0);
+    }
+}
@@ -0,0 +1,408 @@
+use std::collections::{HashMap, HashSet};
+use crate::id::Id;
+use crate::models::{Quiz, SearchHighlight, SearchHit, SearchHitKind};
+
+/// Words too common to be worth indexing. JavaScript keywords such as `for`, `in`
+/// and `do` are deliberately kept.
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "be", "by", "does", "how", "is", "it",
+    "of", "on", "or", "the", "to", "what", "which",
+];
+/// Snippets longer than this are cut down to a window around the first match.
+const SNIPPET_CHARS: usize = 160;
+/// Characters of context kept before the first match in a cut snippet.
+const SNIPPET_LEAD: usize = 60;
+
+/// A search term and the byte range of the text it came from.
+#[derive(Debug, PartialEq)]
+pub struct Token {
+    pub term: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+fn is_ident(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+/// Splits text into lowercase, stemmed terms. Code identifiers are indexed whole and by parts:
+/// `Array.prototype.map` yields `array.prototype.map`, `array`, `prototype` and `map`, and
+/// `forEach` yields `foreach`, `for` and `each`.
+pub fn tokenize(text: &str) -> Vec<Token> {
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        if !is_ident(chars[i].1) {
+            i += 1;
+            continue;
+        }
+        // A word runs over identifier characters, joined by dots between them
+        let start = i;
+        while i < chars.len() && (is_ident(chars[i].1)
+            || (chars[i].1 == '.' && i > start && i + 1 < chars.len() && is_ident(chars[i + 1].1)))
+        {
+            i += 1;
+        }
+        let (from, to) = (chars[start].0, chars.get(i).map_or(text.len(), |c| c.0));
+        let word = &text[from..to];
+        if word.contains('.') {
+            tokens.push(Token { term: word.to_lowercase(), start: from, end: to });
+            let mut offset = from;
+            for part in word.split('.') {
+                push_word(&mut tokens, part, offset);
+                offset += part.len() + 1;
+            }
+        } else {
+            push_word(&mut tokens, word, from);
+        }
+    }
+    tokens
+}
+
+/// Adds a word and, for camelCase or snake_case identifiers, each of its pieces.
+fn push_word(tokens: &mut Vec<Token>, word: &str, offset: usize) {
+    let pieces = split_identifier(word);
+    let mut push = |piece: &str, start: usize| {
+        let lower = piece.to_lowercase();
+        if !lower.is_empty() && !STOP_WORDS.contains(&lower.as_str()) {
+            tokens.push(Token { term: stem(&lower), start, end: start + piece.len() });
+        }
+    };
+    push(word, offset);
+    if pieces.len() > 1 {
+        for (start, piece) in pieces {
+            push(piece, offset + start);
+        }
+    }
+}
+
+fn split_identifier(word: &str) -> Vec<(usize, &str)> {
+    let chars: Vec<(usize, char)> = word.char_indices().collect();
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    for i in 0..chars.len() {
+        let (at, c) = chars[i];
+        if c == '_' || c == '$' {
+            if at > start {
+                pieces.push((start, &word[start..at]));
+            }
+            start = at + c.len_utf8();
+            continue;
+        }
+        let Some(&(_, prev)) = i.checked_sub(1).and_then(|p| chars.get(p)) else {
+            continue;
+        };
+        let next_lower = chars.get(i + 1).is_some_and(|(_, n)| n.is_lowercase());
+        let boundary = c.is_uppercase()
+            && (prev.is_lowercase() || prev.is_ascii_digit() || (prev.is_uppercase() && next_lower));
+        if boundary && at > start {
+            pieces.push((start, &word[start..at]));
+            start = at;
+        }
+    }
+    if start < word.len() {
+        pieces.push((start, &word[start..]));
+    }
+    pieces
+}
+
+fn is_vowel(c: u8) -> bool {
+    matches!(c, b'a' | b'e' | b'i' | b'o' | b'u')
+}
+
+/// A light suffix-stripping stemmer, enough to match plurals and verb forms
+/// ("promises" and "promise", "mapping" and "mapped").
+pub fn stem(word: &str) -> String {
+    if word.len() <= 3 || !word.bytes().all(|b| b.is_ascii_lowercase()) {
+        return word.to_string();
+    }
+    let mut w = word.to_string();
+    if w.ends_with("sses") || w.ends_with("ies") {
+        w.truncate(w.len() - 2);
+    } else if w.ends_with('s') && !(w.ends_with("ss") || w.ends_with("us") || w.ends_with("is")) {
+        w.pop();
+    }
+    for suffix in ["ing", "ed"] {
+        if let Some(base) = w.strip_suffix(suffix)
+            && base.len() >= 3
+            && base.bytes().any(is_vowel)
+        {
+            w.truncate(base.len());
+            let bytes = w.as_bytes();
+            let (last, before) = (bytes[bytes.len() - 1], bytes[bytes.len() - 2]);
+            if last == before && !is_vowel(last) && !matches!(last, b'l' | b's' | b'z') {
+                w.pop();
+            }
+            break;
+        }
+    }
+    let bytes = w.as_bytes();
+    if w.len() > 3 && bytes[bytes.len() - 1] == b'y' && !is_vowel(bytes[bytes.len() - 2]) {
+        w.pop();
+        w.push('i');
+    }
+    if w.len() > 3 && w.ends_with('e') {
+        w.pop();
+    }
+    w
+}
+
+/// Relative importance of a match in each field.
+fn field_weight(field: &str) -> f64 {
+    match field {
+        "title" => 3.0,
+        "question" => 2.0,
+        _ => 1.0,
+    }
+}
+
+struct Document {
+    kind: SearchHitKind,
+    quiz_id: Id,
+    quiz_title: String,
+    question_id: Option<Id>,
+    /// (field name, text)
+    fields: Vec<(&'static str, String)>,
+}
+
+struct Posting {
+    doc: usize,
+    field: usize,
+    frequency: u32,
+}
+
+/// Inverted index over quiz titles and question text, options and explanations.
+/// Explanations are only indexed for quizzes that don't withhold answers.
+pub struct SearchIndex {
+    documents: Vec<Document>,
+    postings: HashMap<String, Vec<Posting>>,
+}
+
+impl SearchIndex {
+    /// Indexes all regular quizzes; generated quizzes only repeat pool questions.
+    pub fn build(quizzes: &[Quiz]) -> Self {
+        let mut documents = Vec::new();
+        for quiz in quizzes.iter().filter(|q| !q.generated) {
+            documents.push(Document {
+                kind: SearchHitKind::Quiz,
+                quiz_id: quiz.id,
+                quiz_title: quiz.title.clone(),
+                question_id: None,
+                fields: vec![("title", quiz.title.clone())],
+            });
+            for question in &quiz.questions {
+                let mut fields = vec![("question", question.text.clone())];
+                fields.extend(question.options.iter().map(|o| ("option", o.text.clone())));
+                // Explanations give answers away, so quizzes that withhold answers keep them out
+                if !quiz.withholds_answers() {
+                    fields.extend(question.explanation.iter().map(|e| ("explanation", e.clone())));
+                }
+                documents.push(Document {
+                    kind: SearchHitKind::Question,
+                    quiz_id: quiz.id,
+                    quiz_title: quiz.title.clone(),
+                    question_id: Some(question.id),
+                    fields,
+                });
+            }
+        }
+
+        let mut postings: HashMap<String, Vec<Posting>> = HashMap::new();
+        for (doc, document) in documents.iter().enumerate() {
+            for (field, (_, text)) in document.fields.iter().enumerate() {
+                let mut frequencies: HashMap<String, u32> = HashMap::new();
+                for token in tokenize(text) {
+                    *frequencies.entry(token.term).or_default() += 1;
+                }
+                for (term, frequency) in frequencies {
+                    postings.entry(term).or_default().push(Posting { doc, field, frequency });
+                }
+            }
+        }
+        SearchIndex { documents, postings }
+    }
+
+    /// Ranks documents by TF-IDF over the query terms, favouring documents that match
+    /// more of them. Returns the total number of matches and the top `limit` hits.
+    pub fn search(&self, query: &str, limit: usize) -> (usize, Vec<SearchHit>) {
+        let mut terms: Vec<String> = tokenize(query).into_iter().map(|t| t.term).collect();
+        terms.sort();
+        terms.dedup();
+
+        let total_docs = self.documents.len() as f64;
+        let mut scores: HashMap<usize, (f64, HashSet<&str>, Vec<usize>)> = HashMap::new();
+        for term in &terms {
+            let Some(postings) = self.postings.get(term) else {
+                continue;
+            };
+            let doc_frequency = postings.iter().map(|p| p.doc).collect::<HashSet<_>>().len() as f64;
+            let idf = (1.0 + total_docs / doc_frequency).ln();
+            for posting in postings {
+                let (name, _) = self.documents[posting.doc].fields[posting.field];
+                let entry = scores.entry(posting.doc).or_default();
+                entry.0 += idf * field_weight(name) * (1.0 + f64::from(posting.frequency).ln());
+                entry.1.insert(term);
+                if !entry.2.contains(&posting.field) {
+                    entry.2.push(posting.field);
+                }
+            }
+        }
+
+        let mut ranked: Vec<(usize, f64, Vec<usize>)> = scores.into_iter()
+            .map(|(doc, (score, matched, fields))| (doc, score * matched.len() as f64 / terms.len() as f64, fields))
+            .collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        let total = ranked.len();
+        let hits = ranked.into_iter().take(limit).map(|(doc, score, mut fields)| {
+            let document = &self.documents[doc];
+            fields.sort();
+            SearchHit {
+                kind: document.kind,
+                quiz_id: document.quiz_id,
+                quiz_title: document.quiz_title.clone(),
+                question_id: document.question_id,
+                score,
+                highlights: fields.into_iter().map(|field| {
+                    let (name, text) = &document.fields[field];
+                    SearchHighlight { field: name.to_string(), snippet: highlight(text, &terms) }
+                }).collect(),
+            }
+        }).collect();
+        (total, hits)
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+fn char_boundary(text: &str, mut index: usize) -> usize {
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+/// HTML-escaped snippet of `text` with matching terms wrapped in `<mark>`.
+/// Long texts are cut to a window around the first match.
+pub fn highlight(text: &str, terms: &[String]) -> String {
+    let mut spans: Vec<(usize, usize)> = tokenize(text).into_iter()
+        .filter(|t| terms.contains(&t.term))
+        .map(|t| (t.start, t.end))
+        .collect();
+    spans.sort();
+    // A whole identifier and its parts overlap; keep the outermost span
+    let mut merged: Vec<(usize, usize)> = Vec::new();
+    for (start, end) in spans {
+        match merged.last_mut() {
+            Some(last) if start < last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+
+    let (mut from, mut to) = (0, text.len());
+    if text.chars().count() > SNIPPET_CHARS {
+        let first = merged.first().map_or(0, |s| s.0);
+        from = char_boundary(text, first.saturating_sub(SNIPPET_LEAD));
+        to = char_boundary(text, (from + SNIPPET_CHARS).min(text.len()));
+    }
+
+    let mut snippet = String::new();
+    if from > 0 {
+        snippet.push('…');
+    }
+    let mut cursor = from;
+    for (start, end) in merged.into_iter().filter(|s| s.0 >= from && s.1 <= to) {
+        snippet.push_str(&escape_html(&text[cursor..start]));
+        snippet.push_str("<mark>");
+        snippet.push_str(&escape_html(&text[start..end]));
+        snippet.push_str("</mark>");
+        cursor = end;
+    }
+    snippet.push_str(&escape_html(&text[cursor..to]));
+    if to < text.len() {
+        snippet.push('…');
+    }
+    snippet
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::models::{Question, RevealPolicy};
+
+    fn terms(text: &str) -> Vec<String> {
+        tokenize(text).into_iter().map(|t| t.term).collect()
+    }
+
+    #[test]
+    fn tokenizes_code_identifiers_whole_and_by_parts() {
+        assert_eq!(terms("Array.prototype.map()"), ["array.prototype.map", "array", "prototyp", "map"]);
+        assert_eq!(terms("forEach"), ["foreach", "for", "each"]);
+        assert_eq!(terms("XMLHttpRequest"), ["xmlhttprequest", "xml", "http", "request"]);
+        assert_eq!(terms("is_array"), ["is_array", "array"]);
+        // A sentence-ending dot doesn't join words
+        assert_eq!(terms("Use map. Then"), ["use", "map", "then"]);
+    }
+
+    #[test]
+    fn stems_plurals_and_verb_forms_together() {
+        for (a, b) in [("promises", "promise"), ("mapping", "mapped"), ("closing", "close"), ("properties", "property"), ("iterating", "iterate")] {
+            assert_eq!(stem(a), stem(b), "{} / {}", a, b);
+        }
+        assert_eq!(stem("class"), "class");
+        assert_eq!(stem("status"), "status");
+    }
+
+    #[test]
+    fn ranks_title_matches_first_and_highlights() {
+        let question = |text: &str, explanation: &str| Question {
+            explanation: Some(explanation.to_string()),
+            ..fixtures::question(text, vec![fixtures::option("map", true)])
+        };
+        let quiz = Quiz {
+            title: "Promises and async".to_string(),
+            ..fixtures::quiz(vec![
+                question("What does a promise resolve to?", "A value"),
+                question("Which method transforms arrays?", "Array.prototype.map returns a <new> array"),
+            ])
+        };
+        let index = SearchIndex::build(std::slice::from_ref(&quiz));
+
+        let (total, hits) = index.search("promise", 10);
+        assert_eq!(total, 2);
+        assert_eq!(hits[0].kind, SearchHitKind::Quiz);
+        assert_eq!(hits[0].highlights[0].snippet, "<mark>Promises</mark> and async");
+
+        let (_, hits) = index.search("array.prototype.map", 10);
+        assert_eq!(hits[0].question_id, Some(quiz.questions[1].id));
+        let explanation = hits[0].highlights.iter().find(|h| h.field == "explanation").unwrap();
+        assert_eq!(explanation.snippet, "<mark>Array.prototype.map</mark> returns a &lt;new&gt; <mark>array</mark>");
+
+        assert_eq!(index.search("nothing here", 10).0, 0);
+    }
+
+    #[test]
+    fn explanations_of_quizzes_withholding_answers_are_not_indexed() {
+        let question = Question {
+            explanation: Some("Closures capture variables".to_string()),
+            ..fixtures::question("What is printed?", vec![fixtures::option("1", true)])
+        };
+        let exam = Quiz { exam_mode: true, ..fixtures::quiz(vec![question.clone()]) };
+        let strict = Quiz { reveal_policy: RevealPolicy::Never, ..fixtures::quiz(vec![question]) };
+        let index = SearchIndex::build(&[exam, strict]);
+        assert_eq!(index.search("closures", 10).0, 0);
+        assert_eq!(index.search("printed", 10).0, 2);
+    }
+
+    #[test]
+    fn long_snippets_are_cut_around_the_first_match() {
+        let text = format!("{} needle {}", "x".repeat(200), "y".repeat(200));
+        let snippet = highlight(&text, &["needl".to_string()]);
+        assert!(snippet.starts_with('…') && snippet.ends_with('…'));
+        assert!(snippet.contains("<mark>needle</mark>"));
+    }
+}
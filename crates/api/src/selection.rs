@@ -0,0 +1,166 @@
+use std::collections::{HashMap, HashSet};
+use rand::Rng;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::seq::SliceRandom;
+use crate::id::Id;
+use crate::models::{AnswerRecord, Difficulty, Question, Quiz};
+
+/// Extra weight given to previously failed questions over unseen ones.
+const FAILED_WEIGHT: f64 = 2.0;
+
+/// Latest correctness per question, in history order.
+pub fn latest_results(history: &[AnswerRecord]) -> HashMap<Id, bool> {
+    history.iter().map(|r| (r.question_id, r.correct)).collect()
+}
+
+/// How much a quiz is worth revisiting: the share of its questions that are unseen,
+/// with failed questions counting double. Quiz size doesn't matter, so large quizzes
+/// don't crowd out small ones.
+fn freshness(quiz: &Quiz, latest: &HashMap<Id, bool>) -> f64 {
+    if quiz.questions.is_empty() {
+        return 0.0;
+    }
+    let weight: f64 = quiz.questions.iter().map(|q| match latest.get(&q.id) {
+        None => 1.0,
+        Some(false) => FAILED_WEIGHT,
+        Some(true) => 0.0,
+    }).sum();
+    weight / quiz.questions.len() as f64
+}
+
+/// Picks a quiz, preferring unseen or previously failed content. Falls back to a
+/// uniform pick once everything has been answered correctly.
+pub fn pick_quiz<'a, R: Rng>(candidates: &[&'a Quiz], history: &[AnswerRecord], rng: &mut R) -> Option<&'a Quiz> {
+    if candidates.is_empty() {
+        return None;
+    }
+    let latest = latest_results(history);
+    let weights: Vec<f64> = candidates.iter().map(|q| freshness(q, &latest)).collect();
+    match WeightedIndex::new(&weights) {
+        Ok(dist) => Some(candidates[dist.sample(rng)]),
+        Err(_) => Some(candidates[rng.gen_range(0..candidates.len())]),
+    }
+}
+
+/// Criteria for drawing questions from the pool.
+#[derive(Debug, Default)]
+pub struct QuestionFilter {
+    /// Matches quizzes with any of these tags; empty matches all
+    pub tags: Vec<String>,
+    pub category_id: Option<Id>,
+    pub difficulty: Option<Difficulty>,
+}
+
+/// All questions of non-generated quizzes matching the filter, without duplicates.
+/// Seed files repeat some questions across quizzes, so duplicates are detected by text as well as ID.
+///
+/// Questions drawn from the pool are served under the default reveal policy, so quizzes in
+/// exam mode or with a stricter policy stay out of it rather than leak their answers.
+pub fn question_pool<'a>(quizzes: &'a [Quiz], filter: &QuestionFilter) -> Vec<(&'a Quiz, &'a Question)> {
+    let mut seen_ids = HashSet::new();
+    let mut seen_texts = HashSet::new();
+    quizzes.iter()
+        .filter(|quiz| !quiz.generated && !quiz.withholds_answers())
+        .filter(|quiz| filter.tags.is_empty() || quiz.tags.iter().any(|t| filter.tags.contains(t)))
+        .filter(|quiz| filter.category_id.is_none() || quiz.category_id == filter.category_id)
+        .flat_map(|quiz| quiz.questions.iter().map(move |q| (quiz, q)))
+        .filter(|(quiz, q)| filter.difficulty.is_none() || quiz.question_difficulty(q) == filter.difficulty)
+        .filter(|(_, q)| seen_ids.insert(q.id) && seen_texts.insert(q.text.trim().to_lowercase()))
+        .collect()
+}
+
+/// Draws up to `count` distinct questions from the pool in random order.
+pub fn draw_questions<'a, R: Rng>(pool: &[(&'a Quiz, &'a Question)], count: usize, rng: &mut R) -> Vec<&'a Question> {
+    pool.choose_multiple(rng, count).map(|(_, q)| *q).collect()
+}
+
+/// Tags of the quizzes the picked questions came from, sorted and deduplicated.
+pub fn source_tags(pool: &[(&Quiz, &Question)], picked: &[Question]) -> Vec<String> {
+    let mut tags: Vec<String> = pool.iter()
+        .filter(|(_, q)| picked.iter().any(|p| p.id == q.id))
+        .flat_map(|(quiz, _)| quiz.tags.iter().cloned())
+        .collect();
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use crate::fixtures;
+    use crate::models::RevealPolicy;
+
+    fn quiz(question_count: usize) -> Quiz {
+        fixtures::quiz((0..question_count).map(|_| fixtures::question("Q", Vec::new())).collect())
+    }
+
+    fn answered(quiz: &Quiz, correct: bool) -> Vec<AnswerRecord> {
+        quiz.questions.iter().map(|q| AnswerRecord {
+            quiz_id: quiz.id,
+            question_id: q.id,
+            option_id: Id::new(),
+            correct,
+            answered_at: Utc::now(),
+        }).collect()
+    }
+
+    #[test]
+    fn mastered_quizzes_are_skipped_while_others_remain() {
+        let mastered = quiz(50);
+        let fresh = quiz(2);
+        let history = answered(&mastered, true);
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..20 {
+            let picked = pick_quiz(&[&mastered, &fresh], &history, &mut rng).unwrap();
+            assert_eq!(picked.id, fresh.id);
+        }
+    }
+
+    #[test]
+    fn falls_back_to_uniform_when_everything_is_mastered() {
+        let a = quiz(1);
+        let history = answered(&a, true);
+        let mut rng = StdRng::seed_from_u64(7);
+        assert_eq!(pick_quiz(&[&a], &history, &mut rng).unwrap().id, a.id);
+        assert!(pick_quiz(&[], &history, &mut rng).is_none());
+    }
+
+    #[test]
+    fn question_pool_skips_generated_quizzes_and_duplicate_texts() {
+        let source = quiz(3);
+        let mut copy = quiz(0);
+        copy.questions = source.questions.clone();
+        copy.generated = true;
+
+        let quizzes = [source.clone(), copy];
+        let pool = question_pool(&quizzes, &QuestionFilter::default());
+        // All three source questions share the text "Q"
+        assert_eq!(pool.len(), 1);
+        assert_eq!(pool[0].0.id, source.id);
+    }
+
+    #[test]
+    fn question_pool_skips_quizzes_that_withhold_answers() {
+        let mut exam = quiz(1);
+        exam.exam_mode = true;
+        let mut never = quiz(1);
+        never.reveal_policy = RevealPolicy::Never;
+        let mut after = quiz(1);
+        after.reveal_policy = RevealPolicy::AfterFinalAttempt;
+
+        assert!(question_pool(&[exam, never, after], &QuestionFilter::default()).is_empty());
+    }
+
+    #[test]
+    fn failed_content_outweighs_unseen_content() {
+        let failed = quiz(1);
+        let unseen = quiz(1);
+        let history = answered(&failed, false);
+        assert_eq!(freshness(&failed, &latest_results(&history)), 2.0);
+        assert_eq!(freshness(&unseen, &latest_results(&history)), 1.0);
+    }
+}
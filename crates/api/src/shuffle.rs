@@ -0,0 +1,66 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use crate::id::Id;
+use crate::models::{Question, Quiz};
+
+/// The quiz's questions in the order one attempt presents them.
+/// The order is derived from the attempt ID, so it stays the same across requests.
+pub fn attempt_questions(quiz: &Quiz, attempt_id: Id) -> Vec<Question> {
+    let mut rng = StdRng::seed_from_u64(attempt_id.to_i64() as u64);
+    let mut questions = quiz.questions.clone();
+    if quiz.shuffle_questions {
+        questions.shuffle(&mut rng);
+    }
+    if quiz.shuffle_options {
+        for question in &mut questions {
+            question.options.shuffle(&mut rng);
+        }
+    }
+    questions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    fn quiz(shuffle_options: bool, shuffle_questions: bool) -> Quiz {
+        let questions = (0..8).map(|i| fixtures::question(
+            &format!("Q{}", i),
+            (0..4).map(|j| fixtures::option(&format!("O{}", j), j == 0)).collect(),
+        )).collect();
+        Quiz { shuffle_options, shuffle_questions, ..fixtures::quiz(questions) }
+    }
+
+    fn option_ids(questions: &[Question]) -> Vec<Id> {
+        questions.iter().flat_map(|q| q.options.iter().map(|o| o.id)).collect()
+    }
+
+    #[test]
+    fn order_is_stable_per_attempt() {
+        let quiz = quiz(true, true);
+        let attempt_id = Id::new();
+        let first = attempt_questions(&quiz, attempt_id);
+        assert_eq!(option_ids(&first), option_ids(&attempt_questions(&quiz, attempt_id)));
+        assert_ne!(option_ids(&first), option_ids(&quiz.questions));
+
+        // Same questions and options, just reordered
+        let mut shuffled = option_ids(&first);
+        let mut original = option_ids(&quiz.questions);
+        shuffled.sort_by_key(|id| id.to_i64());
+        original.sort_by_key(|id| id.to_i64());
+        assert_eq!(shuffled, original);
+    }
+
+    #[test]
+    fn flags_disable_shuffling() {
+        let quiz = quiz(false, false);
+        let questions = attempt_questions(&quiz, Id::new());
+        assert_eq!(option_ids(&questions), option_ids(&quiz.questions));
+
+        let quiz = self::quiz(true, false);
+        let question_ids: Vec<Id> = attempt_questions(&quiz, Id::new()).iter().map(|q| q.id).collect();
+        assert_eq!(question_ids, quiz.questions.iter().map(|q| q.id).collect::<Vec<_>>());
+    }
+}
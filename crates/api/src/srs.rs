@@ -0,0 +1,130 @@
+use chrono::{DateTime, Duration, Utc};
+use crate::id::Id;
+use crate::models::ReviewCard;
+
+/// Ease factor new cards start with.
+pub const INITIAL_EASE: f64 = 2.5;
+/// SM-2 never lets the ease factor drop below this.
+pub const MIN_EASE: f64 = 1.3;
+/// Upper bound on the ease factor, so perfect grades can't grow intervals without limit.
+pub const MAX_EASE: f64 = 3.5;
+/// Longest interval between reviews, about a century.
+pub const MAX_INTERVAL_DAYS: u32 = 36_500;
+/// Highest SM-2 grade ("perfect response").
+pub const MAX_GRADE: u8 = 5;
+/// Grades below this count as a lapse and restart the schedule.
+const PASSING_GRADE: u8 = 3;
+
+/// Grades used for answers given through solving rather than explicit review.
+pub const CORRECT_ANSWER_GRADE: u8 = 4;
+pub const WRONG_ANSWER_GRADE: u8 = 1;
+
+pub fn new_card(quiz_id: Id, question_id: Id, now: DateTime<Utc>) -> ReviewCard {
+    ReviewCard {
+        question_id,
+        quiz_id,
+        ease_factor: INITIAL_EASE,
+        interval_days: 0,
+        repetitions: 0,
+        due_at: now,
+        last_reviewed_at: None,
+    }
+}
+
+/// Applies one SM-2 review with a 0-5 grade.
+///
+/// Passing grades only count once the card is due; reviewing early leaves the schedule as it is.
+/// A lapse always counts, since it shows the answer was forgotten.
+pub fn review(card: &mut ReviewCard, grade: u8, now: DateTime<Utc>) {
+    let grade = grade.min(MAX_GRADE);
+    if grade >= PASSING_GRADE && now < card.due_at {
+        return;
+    }
+    if grade >= PASSING_GRADE {
+        card.interval_days = match card.repetitions {
+            0 => 1,
+            1 => 6,
+            _ => (card.interval_days as f64 * card.ease_factor).round().min(MAX_INTERVAL_DAYS as f64) as u32,
+        };
+        card.repetitions += 1;
+    } else {
+        // A lapse restarts the schedule; the question is due again right away,
+        // like SM-2's same-session repetition of failed items.
+        card.repetitions = 0;
+        card.interval_days = 0;
+    }
+
+    let miss = f64::from(MAX_GRADE - grade);
+    card.ease_factor = (card.ease_factor + 0.1 - miss * (0.08 + miss * 0.02)).clamp(MIN_EASE, MAX_EASE);
+    card.due_at = now.checked_add_signed(Duration::days(card.interval_days.into())).unwrap_or(DateTime::<Utc>::MAX_UTC);
+    card.last_reviewed_at = Some(now);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn successful_reviews_grow_the_interval() {
+        let now = Utc::now();
+        let mut card = new_card(Id::new(), Id::new(), now);
+
+        review(&mut card, 5, now);
+        assert_eq!(card.interval_days, 1);
+        let due = card.due_at;
+        review(&mut card, 5, due);
+        assert_eq!(card.interval_days, 6);
+        let reviewed_at = card.due_at;
+        review(&mut card, 5, reviewed_at);
+        // 6 days * ease 2.7 (2.5 + 0.1 for each of the two earlier reviews)
+        assert_eq!(card.interval_days, 16);
+        assert!((card.ease_factor - 2.8).abs() < 1e-9);
+        assert_eq!(card.due_at, reviewed_at + Duration::days(16));
+    }
+
+    #[test]
+    fn early_reviews_do_not_count() {
+        let now = Utc::now();
+        let mut card = new_card(Id::new(), Id::new(), now);
+        review(&mut card, 5, now);
+        let scheduled = card.clone();
+
+        review(&mut card, 5, now + Duration::hours(1));
+        assert_eq!(card.repetitions, scheduled.repetitions);
+        assert_eq!(card.due_at, scheduled.due_at);
+
+        review(&mut card, 1, now + Duration::hours(1));
+        assert_eq!(card.repetitions, 0);
+    }
+
+    #[test]
+    fn perfect_reviews_are_capped() {
+        let mut now = Utc::now();
+        let mut card = new_card(Id::new(), Id::new(), now);
+        for _ in 0..50 {
+            review(&mut card, 5, now);
+            now = card.due_at;
+        }
+        assert_eq!(card.ease_factor, MAX_EASE);
+        assert_eq!(card.interval_days, MAX_INTERVAL_DAYS);
+    }
+
+    #[test]
+    fn lapses_reset_repetitions_and_lower_ease() {
+        let now = Utc::now();
+        let mut card = new_card(Id::new(), Id::new(), now);
+        review(&mut card, 4, now);
+        review(&mut card, 4, now);
+
+        review(&mut card, 1, now);
+        assert_eq!(card.repetitions, 0);
+        assert_eq!(card.interval_days, 0);
+        assert_eq!(card.due_at, now);
+        assert!(card.ease_factor < INITIAL_EASE);
+
+        for _ in 0..10 {
+            review(&mut card, 0, now);
+        }
+        assert_eq!(card.ease_factor, MIN_EASE);
+    }
+}
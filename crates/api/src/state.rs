@@ -1,7 +1,314 @@
+use chrono::{FixedOffset, NaiveDate, Utc};
+use crate::srs;
+use crate::scoring;
+use crate::daily;
+use crate::achievements::{self, Event, LearnerAchievements, Rule};
+use crate::search::SearchIndex;
+use crate::tags;
+use crate::adaptive::{self, QuestionRating};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::RwLock;
-use crate::models::{Quiz, Category};
+use crate::id::Id;
+use crate::models::{AnswerRecord, Attempt, AttemptAnswer, Quiz, Category, Difficulty, PracticeSession, ReviewCard, Tag};
+
+/// Ad-hoc generated quizzes kept at once; older ones are dropped first.
+pub const MAX_GENERATED_QUIZZES: usize = 200;
 
 pub struct AppState {
     pub quizzes: RwLock<Vec<Quiz>>,
     pub categories: RwLock<Vec<Category>>,
+    /// Tags used by regular quizzes, each with an ID kept while any quiz uses it; quiz tags
+    /// refer to these by name.
+    pub tags: RwLock<Vec<Tag>>,
+    /// Maps each question ID to the quizzes that contain it and their positions in
+    /// `quizzes`, original quiz first. Generated quizzes share question IDs with the
+    /// quizzes they were drawn from.
+    pub question_index: RwLock<HashMap<Id, Vec<(Id, usize)>>>,
+    /// Hints revealed outside attempts since the learner last answered the question,
+    /// keyed by learner and question.
+    pub hint_usage: RwLock<HashMap<(String, Id), usize>>,
+    pub attempts: RwLock<HashMap<Id, Attempt>>,
+    /// Every answer given by each learner, keyed by `learner_key`.
+    pub history: RwLock<HashMap<String, Vec<AnswerRecord>>>,
+    /// Spaced-repetition cards per learner, keyed by question.
+    pub reviews: RwLock<HashMap<String, HashMap<Id, ReviewCard>>>,
+    /// Calibrated difficulty per question; questions without answers use their declared difficulty.
+    pub question_ratings: RwLock<HashMap<Id, QuestionRating>>,
+    /// Ability rating per learner.
+    pub abilities: RwLock<HashMap<String, f64>>,
+    pub practice_sessions: RwLock<HashMap<Id, PracticeSession>>,
+    /// Daily challenge quiz per date.
+    pub daily_quizzes: RwLock<HashMap<NaiveDate, Id>>,
+    /// Daily challenge attempt per learner and date.
+    pub daily_attempts: RwLock<HashMap<String, BTreeMap<NaiveDate, Id>>>,
+    /// UTC offset each learner's days are counted in, pinned by their first daily attempt.
+    pub daily_offsets: RwLock<HashMap<String, FixedOffset>>,
+    /// Rules for awarding badges, loaded once at startup.
+    pub achievement_rules: Vec<Rule>,
+    /// Achievement counters and earned badges per learner.
+    pub achievements: RwLock<HashMap<String, LearnerAchievements>>,
+    /// Full-text index, built on the first search after quizzes change.
+    pub search_index: RwLock<Option<SearchIndex>>,
+}
+
+impl AppState {
+    pub fn new(mut quizzes: Vec<Quiz>, categories: Vec<Category>) -> Self {
+        // Seed files may spell tags any way they like
+        let mut registry = Vec::new();
+        for quiz in &mut quizzes {
+            quiz.tags = tags::normalize_all(&quiz.tags);
+            tags::register(&mut registry, &quiz.tags);
+        }
+        AppState {
+            question_index: RwLock::new(build_question_index(&quizzes)),
+            quizzes: RwLock::new(quizzes),
+            categories: RwLock::new(categories),
+            tags: RwLock::new(registry),
+            hint_usage: RwLock::new(HashMap::new()),
+            attempts: RwLock::new(HashMap::new()),
+            history: RwLock::new(HashMap::new()),
+            reviews: RwLock::new(HashMap::new()),
+            question_ratings: RwLock::new(HashMap::new()),
+            abilities: RwLock::new(HashMap::new()),
+            practice_sessions: RwLock::new(HashMap::new()),
+            daily_quizzes: RwLock::new(HashMap::new()),
+            daily_attempts: RwLock::new(HashMap::new()),
+            daily_offsets: RwLock::new(HashMap::new()),
+            achievement_rules: achievements::load_rules(),
+            achievements: RwLock::new(HashMap::new()),
+            search_index: RwLock::new(None),
+        }
+    }
+
+    /// Rebuilds the question index and drops the search index; call while still
+    /// holding the quizzes write lock after quizzes or questions are added or removed.
+    pub fn reindex_questions(&self, quizzes: &[Quiz]) {
+        if let Ok(mut index) = self.question_index.write() {
+            *index = build_question_index(quizzes);
+        }
+        self.invalidate_search();
+    }
+
+    /// Drops the search index; call after edits that keep every quiz and question
+    /// in place, which leave the question index valid.
+    pub fn invalidate_search(&self) {
+        if let Ok(mut search) = self.search_index.write() {
+            *search = None;
+        }
+    }
+
+    /// Registers tags written to a quiz; call while holding the quizzes write lock.
+    pub fn register_tags(&self, names: &[String]) {
+        if let Ok(mut registry) = self.tags.write() {
+            tags::register(&mut registry, names);
+        }
+    }
+
+    /// Drops tags no quiz uses any more; call while still holding the quizzes write
+    /// lock after quizzes are removed or their tags replaced.
+    pub fn prune_tags(&self, quizzes: &[Quiz]) {
+        if let Ok(mut registry) = self.tags.write() {
+            tags::prune(&mut registry, quizzes);
+        }
+    }
+
+    /// Returns the challenge quiz for a date, generating it on first use.
+    /// Call while holding the quizzes write lock; `None` if the question pool is empty.
+    pub fn daily_quiz(&self, quizzes: &mut Vec<Quiz>, date: NaiveDate) -> Option<Id> {
+        let mut daily = self.daily_quizzes.write().ok()?;
+        // A deleted challenge is regenerated
+        if let Some(id) = daily.get(&date).filter(|id| quizzes.iter().any(|q| q.id == **id)) {
+            return Some(*id);
+        }
+        let quiz = daily::challenge_quiz(quizzes, date)?;
+        let id = quiz.id;
+        quizzes.push(quiz);
+        daily.insert(date, id);
+        self.reindex_questions(quizzes);
+        Some(id)
+    }
+
+    /// Adds an ad-hoc generated quiz, dropping the oldest ones beyond [`MAX_GENERATED_QUIZZES`].
+    /// Daily challenges are kept. Call while holding the quizzes write lock.
+    pub fn store_generated(&self, quizzes: &mut Vec<Quiz>, quiz: Quiz) {
+        quizzes.push(quiz);
+        let daily: HashSet<Id> = self.daily_quizzes.read().map(|d| d.values().copied().collect()).unwrap_or_default();
+        let ad_hoc = |q: &Quiz| q.generated && !daily.contains(&q.id);
+        let excess = quizzes.iter().filter(|q| ad_hoc(q)).count().saturating_sub(MAX_GENERATED_QUIZZES);
+        if excess > 0 {
+            // Quizzes are kept in creation order, so the first ones found are the oldest
+            let oldest: HashSet<Id> = quizzes.iter().filter(|q| ad_hoc(q)).take(excess).map(|q| q.id).collect();
+            quizzes.retain(|q| !oldest.contains(&q.id));
+            self.prune_tags(quizzes);
+        }
+        self.reindex_questions(quizzes);
+    }
+
+    /// Appends an answer to the learner's history, reschedules the question for review
+    /// and counts it towards achievements. `quizzes` is the caller's view of all quizzes.
+    pub fn record_answer(&self, quizzes: &[Quiz], user_email: &str, record: AnswerRecord) {
+        let key = learner_key(user_email);
+        let grade = if record.correct { srs::CORRECT_ANSWER_GRADE } else { srs::WRONG_ANSWER_GRADE };
+        self.grade_review(&key, record.quiz_id, record.question_id, grade);
+        // Generated quizzes carry the tags of all their sources; the question only has its original quiz's
+        let original = self.question_index.read().ok()
+            .and_then(|index| index.get(&record.question_id).and_then(|owners| owners.first()).map(|&(_, position)| position))
+            .and_then(|position| quizzes.get(position));
+        let tags = original.map(|q| q.tags.as_slice()).unwrap_or_default();
+        self.record_event(&key, Event::Answered { tags, correct: record.correct });
+        if let Ok(mut history) = self.history.write() {
+            history.entry(key).or_default().push(record);
+        }
+    }
+
+    /// Feeds an attempt answer to the question's rating and, for known learners, their history and reviews.
+    pub fn record_attempt_answer(&self, quizzes: &[Quiz], quiz: &Quiz, user_email: Option<&str>, answer: &AttemptAnswer) {
+        let difficulty = quiz.questions.iter().find(|q| q.id == answer.question_id).and_then(|q| quiz.question_difficulty(q));
+        self.rate_answer(user_email, answer.question_id, difficulty, answer.correct);
+        if let Some(email) = user_email {
+            self.record_answer(quizzes, email, AnswerRecord {
+                quiz_id: quiz.id,
+                question_id: answer.question_id,
+                option_id: answer.option_id,
+                correct: answer.correct,
+                answered_at: answer.answered_at,
+            });
+        }
+    }
+
+    /// Records what an attempt earns once it leaves `InProgress`, whether finished,
+    /// expired on access or expired by the sweeper: the exam answers held back until
+    /// now and the finish and daily completion events. Call once, right after the
+    /// status changes, while holding the quizzes lock and the attempts write lock.
+    pub fn settle_attempt(&self, quizzes: &[Quiz], attempts: &HashMap<Id, Attempt>, attempt_id: Id) {
+        let Some(attempt) = attempts.get(&attempt_id) else { return };
+        let Some(quiz) = quizzes.iter().find(|q| q.id == attempt.quiz_id) else { return };
+        if quiz.exam_mode {
+            for answer in &attempt.answers {
+                self.record_attempt_answer(quizzes, quiz, attempt.user_email.as_deref(), answer);
+            }
+        }
+
+        let Some(email) = attempt.user_email.as_deref() else { return };
+        let result = scoring::score_attempt(quiz, attempt);
+        let perfect = result.max_score > 0.0 && result.score == result.max_score;
+        self.record_event(email, Event::AttemptFinished { perfect });
+
+        let Ok(daily_attempts) = self.daily_attempts.read() else { return };
+        if let Some(dates) = daily_attempts.get(&learner_key(email))
+            && let Some((&date, _)) = dates.iter().find(|(_, id)| **id == attempt_id)
+        {
+            let (streak, _) = daily::streaks(&daily::completed_days(dates, attempts), date);
+            self.record_event(email, Event::DailyCompleted { streak });
+        }
+    }
+
+    /// Feeds an event to the achievement rules, awarding any badges it unlocks.
+    pub fn record_event(&self, user_email: &str, event: Event) {
+        let Ok(registry) = self.tags.read() else { return };
+        if let Ok(mut learners) = self.achievements.write() {
+            learners.entry(learner_key(user_email)).or_default()
+                .record(event, &self.achievement_rules, &registry);
+        }
+    }
+
+    /// Applies a review grade to the learner's card for a question, creating it if needed.
+    pub fn grade_review(&self, user_email: &str, quiz_id: Id, question_id: Id, grade: u8) -> Option<ReviewCard> {
+        let now = Utc::now();
+        let mut reviews = self.reviews.write().ok()?;
+        let card = reviews.entry(learner_key(user_email)).or_default()
+            .entry(question_id)
+            .or_insert_with(|| srs::new_card(quiz_id, question_id, now));
+        srs::review(card, grade, now);
+        Some(card.clone())
+    }
+
+    /// Calibrates the question's rating from an answer and, for known learners, updates their ability.
+    /// Returns the learner's ability after the answer.
+    pub fn rate_answer(&self, user_email: Option<&str>, question_id: Id, difficulty: Option<Difficulty>, correct: bool) -> f64 {
+        let Ok(mut ratings) = self.question_ratings.write() else {
+            return adaptive::INITIAL_ABILITY;
+        };
+        let Ok(mut abilities) = self.abilities.write() else {
+            return adaptive::INITIAL_ABILITY;
+        };
+        let rating = ratings.entry(question_id).or_insert_with(|| QuestionRating::prior(difficulty));
+        match user_email {
+            Some(email) => {
+                let ability = abilities.entry(learner_key(email)).or_insert(adaptive::INITIAL_ABILITY);
+                *ability = adaptive::rate(*ability, rating, correct);
+                *ability
+            }
+            // Anonymous answers still calibrate the question against an average learner
+            None => adaptive::rate(adaptive::INITIAL_ABILITY, rating, correct),
+        }
+    }
+
+    /// Finalizes every in-progress attempt whose time limit has run out, settling each one.
+    pub fn expire_overdue_attempts(&self) -> usize {
+        let now = Utc::now();
+        let Ok(quizzes) = self.quizzes.read() else { return 0 };
+        let Ok(mut attempts) = self.attempts.write() else { return 0 };
+        let expired: Vec<Id> = attempts.values_mut()
+            .filter_map(|a| a.expire_if_overdue(now).then_some(a.id))
+            .collect();
+        for &attempt_id in &expired {
+            self.settle_attempt(&quizzes, &attempts, attempt_id);
+        }
+        expired.len()
+    }
+}
+
+fn build_question_index(quizzes: &[Quiz]) -> HashMap<Id, Vec<(Id, usize)>> {
+    let mut index: HashMap<Id, Vec<(Id, usize)>> = HashMap::new();
+    let originals = quizzes.iter().enumerate().filter(|(_, q)| !q.generated);
+    let generated = quizzes.iter().enumerate().filter(|(_, q)| q.generated);
+    for (position, quiz) in originals.chain(generated) {
+        for question in &quiz.questions {
+            index.entry(question.id).or_default().push((quiz.id, position));
+        }
+    }
+    index
+}
+
+/// Normalizes a user email so it can be used as a map key.
+pub fn learner_key(email: &str) -> String {
+    email.trim().to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::achievements::Condition;
+    use crate::fixtures;
+
+    #[test]
+    fn answers_count_towards_the_tags_of_their_own_quiz() {
+        let question = |text: &str| fixtures::question(text, vec![fixtures::option("Yes", true)]);
+        let rust = Quiz { tags: vec!["rust".to_string()], ..fixtures::quiz(vec![question("Q1")]) };
+        let dom = Quiz { tags: vec!["dom".to_string()], ..fixtures::quiz(vec![question("Q2")]) };
+        let generated = Quiz {
+            tags: vec!["rust".to_string(), "dom".to_string()],
+            generated: true,
+            ..fixtures::quiz([rust.questions.clone(), dom.questions.clone()].concat())
+        };
+        let (generated_id, question) = (generated.id, rust.questions[0].clone());
+        let mut state = AppState::new(vec![rust, dom, generated], Vec::new());
+        state.achievement_rules = vec![Rule {
+            id: "one_dom".to_string(),
+            name: "One DOM".to_string(),
+            description: "Answer a DOM question".to_string(),
+            condition: Condition::Answers { count: 1, tag: Some("dom".to_string()), correct_only: false },
+        }];
+
+        let quizzes = state.quizzes.read().unwrap().clone();
+        state.record_answer(&quizzes, "learner@example.com", AnswerRecord {
+            quiz_id: generated_id,
+            question_id: question.id,
+            option_id: question.options[0].id,
+            correct: true,
+            answered_at: Utc::now(),
+        });
+        assert!(state.achievements.read().unwrap()["learner@example.com"].badges.is_empty());
+    }
 }
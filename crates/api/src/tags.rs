@@ -0,0 +1,118 @@
+use crate::id::Id;
+use crate::models::{Quiz, Tag};
+
+/// Alternative spellings mapped to the tag they stand for, both in slug form.
+const ALIASES: &[(&str, &str)] = &[
+    ("es2015", "es6"),
+    ("ecmascript-6", "es6"),
+    ("js", "javascript"),
+    ("ts", "typescript"),
+    ("golang", "go"),
+    ("rustlang", "rust"),
+];
+
+/// Converts a tag to its canonical form: lowercase slug with runs of anything other than
+/// letters, digits and underscores collapsed to a single `-`, with aliases resolved.
+/// Returns `None` for tags with nothing left after slugging.
+pub fn normalize(raw: &str) -> Option<String> {
+    let mut slug = String::with_capacity(raw.len());
+    for c in raw.trim().chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() || c == '_' {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        return None;
+    }
+    let canonical = ALIASES.iter()
+        .find(|(alias, _)| *alias == slug)
+        .map_or(slug, |(_, tag)| *tag);
+    Some(canonical.to_string())
+}
+
+/// Normalizes a list of tags, dropping empty ones and duplicates while keeping the first occurrence order.
+pub fn normalize_all<'a>(raw: impl IntoIterator<Item = &'a String>) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in raw.into_iter().filter_map(|t| normalize(t)) {
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+/// Adds a `Tag` entity for every name not registered yet; existing tags keep their IDs.
+pub fn register(registry: &mut Vec<Tag>, names: &[String]) {
+    for name in names {
+        if !registry.iter().any(|t| t.name == *name) {
+            registry.push(Tag { id: Id::new(), name: name.clone() });
+        }
+    }
+}
+
+/// Drops `Tag` entities no regular quiz carries any more, so the registry holds exactly
+/// the tags in use. Generated quizzes only repeat the tags of their source quizzes.
+pub fn prune(registry: &mut Vec<Tag>, quizzes: &[Quiz]) {
+    registry.retain(|tag| quizzes.iter().any(|q| !q.generated && q.tags.contains(&tag.name)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(tags: &[&str]) -> Vec<String> {
+        tags.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn normalize_slugs_tags() {
+        assert_eq!(normalize("  Rust ").as_deref(), Some("rust"));
+        assert_eq!(normalize("Event Loop").as_deref(), Some("event-loop"));
+        assert_eq!(normalize("node.js").as_deref(), Some("node-js"));
+        assert_eq!(normalize("--Async  / Await--").as_deref(), Some("async-await"));
+        assert_eq!(normalize("new_tag1").as_deref(), Some("new_tag1"));
+        assert_eq!(normalize(" -/ "), None);
+    }
+
+    #[test]
+    fn normalize_resolves_aliases() {
+        assert_eq!(normalize("ES2015").as_deref(), Some("es6"));
+        assert_eq!(normalize("ECMAScript 6").as_deref(), Some("es6"));
+        assert_eq!(normalize("JS").as_deref(), Some("javascript"));
+    }
+
+    #[test]
+    fn normalize_all_deduplicates_in_order() {
+        let tags = strings(&["Rust", "rust", "programming", " RUST", "", "es6", "es2015"]);
+        assert_eq!(normalize_all(&tags), strings(&["rust", "programming", "es6"]));
+    }
+
+    #[test]
+    fn register_keeps_existing_ids() {
+        let mut registry = Vec::new();
+        register(&mut registry, &strings(&["rust", "async"]));
+        let rust_id = registry[0].id;
+        register(&mut registry, &strings(&["async", "rust", "dom"]));
+        assert_eq!(registry.len(), 3);
+        assert_eq!(registry[0].id, rust_id);
+        assert_eq!(registry[2].name, "dom");
+    }
+
+    #[test]
+    fn prune_drops_unused_tags() {
+        let mut registry = Vec::new();
+        register(&mut registry, &strings(&["rust", "async", "dom"]));
+        let async_id = registry[1].id;
+        let mut quiz = crate::fixtures::quiz(Vec::new());
+        quiz.tags = strings(&["async"]);
+        let mut generated = crate::fixtures::quiz(Vec::new());
+        generated.tags = strings(&["rust"]);
+        generated.generated = true;
+        prune(&mut registry, &[quiz, generated]);
+        assert_eq!(registry.len(), 1);
+        assert_eq!(registry[0].id, async_id);
+    }
+}
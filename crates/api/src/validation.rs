@@ -0,0 +1,392 @@
+use std::collections::HashSet;
+use std::fmt::Display;
+use crate::daily;
+use crate::models::{
+    AttemptAnswerRequest, CreateCategoryRequest, CreateOptionRequest, CreateQuestionRequest, CreateQuizRequest, FieldError,
+    GenerateQuizRequest, PracticeAnswerRequest, Question, QuestionOption, Quiz, ReorderRequest,
+    ReviewGradeRequest, StartAttemptRequest,
+    StartDailyRequest, StartPracticeRequest, SubmitAnswerRequest, UpdateCategoryRequest,
+    UpdateOptionRequest, UpdateQuestionRequest, UpdateQuizRequest,
+};
+use crate::srs::MAX_GRADE;
+
+pub const MAX_TITLE_LEN: usize = 200;
+pub const MAX_NAME_LEN: usize = 100;
+pub const MAX_QUESTION_LEN: usize = 2_000;
+pub const MAX_OPTION_LEN: usize = 500;
+pub const MAX_EXPLANATION_LEN: usize = 10_000;
+pub const MAX_HINT_LEN: usize = 500;
+pub const MAX_HINTS: usize = 10;
+pub const MAX_TAG_LEN: usize = 50;
+pub const MAX_TAGS: usize = 20;
+pub const MAX_EMAIL_LEN: usize = 254;
+/// Longest time limit or estimate, one day.
+pub const MAX_SECONDS: u32 = 86_400;
+/// Upper bound on the number of questions in a generated quiz.
+pub const MAX_GENERATED_QUESTIONS: usize = 100;
+
+/// Field rules for a request body or stored model.
+pub trait Validate {
+    fn validate(&self, v: &mut Validator);
+}
+
+/// Runs the rules of `value`, collecting every failure rather than stopping at the first.
+pub fn validate<T: Validate + ?Sized>(value: &T) -> Result<(), Vec<FieldError>> {
+    let mut validator = Validator::default();
+    value.validate(&mut validator);
+    if validator.errors.is_empty() { Ok(()) } else { Err(validator.errors) }
+}
+
+/// Collects failed rules under field paths such as `questions[0].options[1].text`.
+#[derive(Default)]
+pub struct Validator {
+    prefix: String,
+    errors: Vec<FieldError>,
+}
+
+impl Validator {
+    fn path(&self, field: &str) -> String {
+        if self.prefix.is_empty() { field.to_string() } else { format!("{}.{}", self.prefix, field) }
+    }
+
+    fn fail(&mut self, field: &str, message: String) {
+        let field = self.path(field);
+        self.errors.push(FieldError { field, message });
+    }
+
+    /// Fails `field` with `message` unless `ok` holds.
+    pub fn rule(&mut self, field: &str, ok: bool, message: &str) {
+        if !ok {
+            self.fail(field, message.to_string());
+        }
+    }
+
+    /// Required text: not blank and at most `max` characters.
+    pub fn text(&mut self, field: &str, value: &str, max: usize) {
+        if value.trim().is_empty() {
+            self.fail(field, "must not be empty".to_string());
+        } else {
+            self.max_len(field, Some(value), max);
+        }
+    }
+
+    /// Like [`Validator::text`], but only when the value is given.
+    pub fn optional_text(&mut self, field: &str, value: Option<&str>, max: usize) {
+        if let Some(value) = value {
+            self.text(field, value, max);
+        }
+    }
+
+    /// Text that may be blank but not longer than `max` characters.
+    pub fn max_len(&mut self, field: &str, value: Option<&str>, max: usize) {
+        if value.is_some_and(|value| value.chars().count() > max) {
+            self.fail(field, format!("must be at most {} characters", max));
+        }
+    }
+
+    pub fn range<T: PartialOrd + Display>(&mut self, field: &str, value: Option<T>, min: T, max: T) {
+        if value.is_some_and(|value| value < min || value > max) {
+            self.fail(field, format!("must be between {} and {}", min, max));
+        }
+    }
+
+    pub fn max_items(&mut self, field: &str, len: usize, max: usize) {
+        if len > max {
+            self.fail(field, format!("must have at most {} items", max));
+        }
+    }
+
+    /// A list of at most `max_items` strings, each checked with [`Validator::text`].
+    pub fn texts(&mut self, field: &str, values: &[String], max_items: usize, max_len: usize) {
+        self.max_items(field, values.len(), max_items);
+        for (i, value) in values.iter().enumerate() {
+            self.text(&format!("{}[{}]", field, i), value, max_len);
+        }
+    }
+
+    /// Tags may be blank, since normalizing drops those, but not too many or too long.
+    pub fn tags(&mut self, field: &str, values: &[String]) {
+        self.max_items(field, values.len(), MAX_TAGS);
+        for (i, value) in values.iter().enumerate() {
+            self.max_len(&format!("{}[{}]", field, i), Some(value), MAX_TAG_LEN);
+        }
+    }
+
+    /// Runs the rules of each item with its index in the field path.
+    pub fn each<T: Validate>(&mut self, field: &str, items: &[T]) {
+        for (i, item) in items.iter().enumerate() {
+            let nested = self.path(&format!("{}[{}]", field, i));
+            let outer = std::mem::replace(&mut self.prefix, nested);
+            item.validate(self);
+            self.prefix = outer;
+        }
+    }
+
+    /// A question needs at least one option, and at least one of them correct.
+    pub fn answers(&mut self, field: &str, mut correct: impl ExactSizeIterator<Item = bool>) {
+        if correct.len() == 0 {
+            self.fail(field, "must have at least one option".to_string());
+        } else if !correct.any(|is_correct| is_correct) {
+            self.fail(field, "must have at least one correct option".to_string());
+        }
+    }
+}
+
+impl Validate for CreateQuizRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.text("title", &self.title, MAX_TITLE_LEN);
+        v.tags("tags", self.tags.as_deref().unwrap_or_default());
+        v.range("time_limit_seconds", self.time_limit_seconds, 1, MAX_SECONDS);
+        v.each("questions", &self.questions);
+    }
+}
+
+impl Validate for UpdateQuizRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.optional_text("title", self.title.as_deref(), MAX_TITLE_LEN);
+        v.tags("tags", self.tags.as_deref().unwrap_or_default());
+        v.range("time_limit_seconds", self.time_limit_seconds, 1, MAX_SECONDS);
+    }
+}
+
+impl Validate for CreateQuestionRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.text("text", &self.text, MAX_QUESTION_LEN);
+        v.max_len("explanation", self.explanation.as_deref(), MAX_EXPLANATION_LEN);
+        v.range("estimated_time_seconds", self.estimated_time_seconds, 1, MAX_SECONDS);
+        v.range("time_limit_seconds", self.time_limit_seconds, 1, MAX_SECONDS);
+        v.texts("hints", self.hints.as_deref().unwrap_or_default(), MAX_HINTS, MAX_HINT_LEN);
+        v.answers("options", self.options.iter().map(|o| o.is_correct));
+        v.each("options", &self.options);
+    }
+}
+
+impl Validate for UpdateQuestionRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.optional_text("text", self.text.as_deref(), MAX_QUESTION_LEN);
+        v.max_len("explanation", self.explanation.as_deref(), MAX_EXPLANATION_LEN);
+        v.range("estimated_time_seconds", self.estimated_time_seconds, 1, MAX_SECONDS);
+        v.range("time_limit_seconds", self.time_limit_seconds, 1, MAX_SECONDS);
+        v.texts("hints", self.hints.as_deref().unwrap_or_default(), MAX_HINTS, MAX_HINT_LEN);
+    }
+}
+
+impl Validate for CreateOptionRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.text("text", &self.text, MAX_OPTION_LEN);
+        v.max_len("description", self.description.as_deref(), MAX_EXPLANATION_LEN);
+    }
+}
+
+impl Validate for UpdateOptionRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.optional_text("text", self.text.as_deref(), MAX_OPTION_LEN);
+        v.max_len("description", self.description.as_deref(), MAX_EXPLANATION_LEN);
+    }
+}
+
+impl Validate for CreateCategoryRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.text("name", &self.name, MAX_NAME_LEN);
+    }
+}
+
+impl Validate for UpdateCategoryRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.optional_text("name", self.name.as_deref(), MAX_NAME_LEN);
+    }
+}
+
+impl Validate for GenerateQuizRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.optional_text("title", self.title.as_deref(), MAX_TITLE_LEN);
+        v.range("count", Some(self.count), 1, MAX_GENERATED_QUESTIONS);
+        v.tags("tags", self.tags.as_deref().unwrap_or_default());
+    }
+}
+
+impl Validate for SubmitAnswerRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.optional_text("user_email", self.user_email.as_deref(), MAX_EMAIL_LEN);
+    }
+}
+
+/// Answers within an attempt or practice session carry only IDs, which the handlers
+/// look up; they have no field rules of their own.
+impl Validate for AttemptAnswerRequest {
+    fn validate(&self, _: &mut Validator) {}
+}
+
+impl Validate for PracticeAnswerRequest {
+    fn validate(&self, _: &mut Validator) {}
+}
+
+/// Whether the IDs name every existing item is checked against the quiz.
+impl Validate for ReorderRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.rule("ids", !self.ids.is_empty(), "must not be empty");
+        let unique: HashSet<_> = self.ids.iter().collect();
+        v.rule("ids", unique.len() == self.ids.len(), "must not repeat an ID");
+    }
+}
+
+impl Validate for StartAttemptRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.optional_text("user_email", self.user_email.as_deref(), MAX_EMAIL_LEN);
+    }
+}
+
+impl Validate for ReviewGradeRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.text("user_email", &self.user_email, MAX_EMAIL_LEN);
+        v.range("grade", Some(self.grade), 0, MAX_GRADE);
+    }
+}
+
+impl Validate for StartPracticeRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.text("user_email", &self.user_email, MAX_EMAIL_LEN);
+        v.tags("tags", &self.tags);
+    }
+}
+
+impl Validate for StartDailyRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.text("user_email", &self.user_email, MAX_EMAIL_LEN);
+        v.rule("tz", daily::parse_offset(self.tz.as_deref()).is_some(), "must be a UTC offset such as +02:00");
+    }
+}
+
+/// Stored quizzes follow the same rules as created ones, so PATCH results and seed files
+/// are held to them too.
+impl Validate for Quiz {
+    fn validate(&self, v: &mut Validator) {
+        v.text("title", &self.title, MAX_TITLE_LEN);
+        v.tags("tags", &self.tags);
+        v.range("time_limit_seconds", self.time_limit_seconds, 1, MAX_SECONDS);
+        v.each("questions", &self.questions);
+    }
+}
+
+impl Validate for Question {
+    fn validate(&self, v: &mut Validator) {
+        v.text("text", &self.text, MAX_QUESTION_LEN);
+        v.max_len("explanation", self.explanation.as_deref(), MAX_EXPLANATION_LEN);
+        v.range("estimated_time_seconds", self.estimated_time_seconds, 1, MAX_SECONDS);
+        v.range("time_limit_seconds", self.time_limit_seconds, 1, MAX_SECONDS);
+        v.texts("hints", &self.hints, MAX_HINTS, MAX_HINT_LEN);
+        v.answers("options", self.options.iter().map(|o| o.is_correct));
+        v.each("options", &self.options);
+    }
+}
+
+impl Validate for QuestionOption {
+    fn validate(&self, v: &mut Validator) {
+        v.text("text", &self.text, MAX_OPTION_LEN);
+        v.max_len("description", self.description.as_deref(), MAX_EXPLANATION_LEN);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn option(text: &str, is_correct: bool) -> CreateOptionRequest {
+        CreateOptionRequest { text: text.to_string(), is_correct, description: None }
+    }
+
+    fn question(text: &str, options: Vec<CreateOptionRequest>) -> CreateQuestionRequest {
+        CreateQuestionRequest {
+            text: text.to_string(),
+            options,
+            explanation: None,
+            difficulty: None,
+            estimated_time_seconds: None,
+            time_limit_seconds: None,
+            hints: None,
+        }
+    }
+
+    fn quiz(title: &str, questions: Vec<CreateQuestionRequest>) -> CreateQuizRequest {
+        CreateQuizRequest {
+            title: title.to_string(),
+            category_id: None,
+            questions,
+            tags: None,
+            difficulty: None,
+            reveal_policy: None,
+            time_limit_seconds: None,
+            exam_mode: None,
+            shuffle_options: None,
+            shuffle_questions: None,
+        }
+    }
+
+    fn fields(errors: Vec<FieldError>) -> Vec<String> {
+        errors.into_iter().map(|e| e.field).collect()
+    }
+
+    #[test]
+    fn accepts_a_valid_quiz() {
+        let request = quiz("Closures", vec![question("What is 2 + 2?", vec![option("4", true), option("5", false)])]);
+        assert!(validate(&request).is_ok());
+        // Drafts may start without questions
+        assert!(validate(&quiz("Draft", Vec::new())).is_ok());
+    }
+
+    #[test]
+    fn reports_every_failure_with_its_path() {
+        let mut request = quiz("  ", vec![
+            question("Fine", vec![option("A", true)]),
+            question("", vec![option("A", false), option(" ", false)]),
+            question("No options", Vec::new()),
+        ]);
+        request.tags = Some(vec!["rust".to_string(), "x".repeat(MAX_TAG_LEN + 1)]);
+        request.time_limit_seconds = Some(0);
+
+        let errors = validate(&request).unwrap_err();
+        assert_eq!(errors[0].message, "must not be empty");
+        assert_eq!(fields(errors), [
+            "title",
+            "tags[1]",
+            "time_limit_seconds",
+            "questions[1].text",
+            "questions[1].options",
+            "questions[1].options[1].text",
+            "questions[2].options",
+        ]);
+    }
+
+    #[test]
+    fn limits_lengths_in_characters() {
+        assert!(validate(&quiz(&"é".repeat(MAX_TITLE_LEN), Vec::new())).is_ok());
+        let errors = validate(&quiz(&"A".repeat(1000), Vec::new())).unwrap_err();
+        assert_eq!(errors[0].field, "title");
+        assert_eq!(errors[0].message, "must be at most 200 characters");
+    }
+
+    #[test]
+    fn distinguishes_missing_options_from_missing_correct_ones() {
+        let none = validate(&question("Q", Vec::new())).unwrap_err();
+        assert_eq!(none[0].message, "must have at least one option");
+        let wrong = validate(&question("Q", vec![option("A", false)])).unwrap_err();
+        assert_eq!(wrong[0].message, "must have at least one correct option");
+        // More than one correct option is allowed
+        assert!(validate(&question("Q", vec![option("A", true), option("B", true)])).is_ok());
+    }
+
+    #[test]
+    fn updates_only_check_given_fields() {
+        let request = UpdateQuestionRequest {
+            text: None,
+            explanation: None,
+            difficulty: None,
+            estimated_time_seconds: None,
+            time_limit_seconds: None,
+            hints: None,
+        };
+        assert!(validate(&request).is_ok());
+        let request = UpdateQuestionRequest { text: Some(String::new()), hints: Some(vec![String::new()]), ..request };
+        assert_eq!(fields(validate(&request).unwrap_err()), ["text", "hints[0]"]);
+    }
+}
@@ -0,0 +1,61 @@
+use crate::common::spawn_app;
+
+mod common;
+
+async fn achievements(app: &common::TestApp, user_email: &str) -> serde_json::Value {
+    app.api_client
+        .get(format!("{}/me/achievements?user_email={}", &app.address, user_email))
+        .send()
+        .await
+        .expect("Failed to fetch achievements")
+        .json()
+        .await
+        .unwrap()
+}
+
+fn earned_ids(report: &serde_json::Value) -> Vec<&str> {
+    report["earned"].as_array().unwrap().iter().map(|b| b["id"].as_str().unwrap()).collect()
+}
+
+#[tokio::test]
+async fn finishing_a_perfect_attempt_awards_badges() {
+    let app = spawn_app().await;
+    let quiz = common::quiz("Badge Quiz").tags(&["async"]).questions(1).create(&app).await;
+    let quiz_id = &quiz.id;
+    let (question_id, right, _) = &quiz.questions[0];
+
+    let user = "learner@example.com";
+    let report = achievements(&app, user).await;
+    assert!(earned_ids(&report).is_empty());
+    assert!(report["locked"].as_array().unwrap().iter().any(|a| a["id"] == "first_perfect_score"));
+
+    let attempt: serde_json::Value = app.api_client
+        .post(format!("{}/quizzes/{}/attempts", &app.address, quiz_id))
+        .json(&serde_json::json!({ "user_email": user }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let attempt_id = attempt["id"].as_str().unwrap();
+    app.api_client
+        .post(format!("{}/quizzes/{}/attempts/{}/answers", &app.address, quiz_id, attempt_id))
+        .json(&serde_json::json!({ "question_id": question_id, "option_id": right }))
+        .send()
+        .await
+        .unwrap();
+    for _ in 0..2 {
+        // Finishing twice must not count twice
+        app.api_client
+            .post(format!("{}/quizzes/{}/attempts/{}/finish", &app.address, quiz_id, attempt_id))
+            .send()
+            .await
+            .unwrap();
+    }
+
+    let report = achievements(&app, "Learner@Example.com").await;
+    assert_eq!(earned_ids(&report), ["first_perfect_score", "first_attempt"]);
+    assert!(report["earned"][0]["awarded_at"].is_string());
+    assert!(!report["locked"].as_array().unwrap().iter().any(|a| a["id"] == "first_perfect_score"));
+}
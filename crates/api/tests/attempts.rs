@@ -0,0 +1,399 @@
+use crate::common::spawn_app;
+
+mod common;
+
+/// Three questions; the first has an explanation and a hint.
+async fn create_quiz(app: &common::TestApp, reveal_policy: &str) -> common::QuizFixture {
+    common::quiz("Attempt Quiz")
+        .with("reveal_policy", serde_json::json!(reveal_policy))
+        .question(serde_json::json!({ "text": "Q1", "explanation": "E1", "hints": ["H1"] }))
+        .question(serde_json::json!({ "text": "Q2" }))
+        .question(serde_json::json!({ "text": "Q3" }))
+        .create(app)
+        .await
+}
+
+async fn start_attempt(app: &common::TestApp, quiz_id: &str) -> String {
+    let response = app.api_client
+        .post(format!("{}/quizzes/{}/attempts", &app.address, quiz_id))
+        .json(&serde_json::json!({ "user_email": "learner@example.com" }))
+        .send()
+        .await
+        .expect("Failed to start attempt");
+    assert_eq!(201, response.status().as_u16());
+    let attempt: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(attempt["status"], "in_progress");
+    attempt["id"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn attempt_lifecycle_returns_score_and_breakdown() {
+    let app = spawn_app().await;
+    let quiz = create_quiz(&app, "after_final_attempt").await;
+    let attempt_id = start_attempt(&app, &quiz.id).await;
+    let attempt_url = format!("{}/quizzes/{}/attempts/{}", &app.address, quiz.id, attempt_id);
+
+    // Reveal a hint for Q1 within the attempt
+    let (q1, q1_right, _) = &quiz.questions[0];
+    let response = app.api_client
+        .get(format!("{}/quizzes/{}/questions/{}/hints/next", &app.address, quiz.id, q1))
+        .query(&[("attempt_id", attempt_id.as_str())])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(200, response.status().as_u16());
+
+    // Q1 right (with hint), Q2 wrong, Q3 unanswered
+    let answer: serde_json::Value = app.api_client
+        .post(format!("{}/answers", &attempt_url))
+        .json(&serde_json::json!({ "question_id": q1, "option_id": q1_right }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(answer["correct"], true);
+    assert_eq!(answer["score"], 0.75);
+
+    let (q2, _, q2_wrong) = &quiz.questions[1];
+    let answer: serde_json::Value = app.api_client
+        .post(format!("{}/answers", &attempt_url))
+        .json(&serde_json::json!({ "question_id": q2, "option_id": q2_wrong }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(answer["correct"], false);
+    assert!(answer.get("correct_options").is_none());
+
+    // Answering the same question twice is rejected
+    let response = app.api_client
+        .post(format!("{}/answers", &attempt_url))
+        .json(&serde_json::json!({ "question_id": q2, "option_id": q2_wrong }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(409, response.status().as_u16());
+
+    let response = app.api_client
+        .post(format!("{}/finish", &attempt_url))
+        .send()
+        .await
+        .expect("Failed to finish attempt");
+    assert_eq!(200, response.status().as_u16());
+    let result: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(result["score"], 0.75);
+    assert_eq!(result["max_score"], 3.0);
+    assert_eq!(result["correct_count"], 1);
+    assert_eq!(result["total_questions"], 3);
+    assert!(result["duration_seconds"].as_i64().unwrap() >= 0);
+
+    let breakdown = result["breakdown"].as_array().unwrap();
+    assert_eq!(breakdown[0]["hints_used"], 1);
+    assert_eq!(breakdown[1]["correct"], false);
+    assert_eq!(breakdown[2]["answered"], false);
+    // after_final_attempt reveals the correct options once finished
+    assert_eq!(breakdown[1]["correct_options"][0]["text"], "Right");
+
+    // Finished attempts accept no more answers
+    let (q3, q3_right, _) = &quiz.questions[2];
+    let response = app.api_client
+        .post(format!("{}/answers", &attempt_url))
+        .json(&serde_json::json!({ "question_id": q3, "option_id": q3_right }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(409, response.status().as_u16());
+
+    let attempt: serde_json::Value = app.api_client
+        .get(&attempt_url)
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(attempt["status"], "finished");
+    assert_eq!(attempt["answers"].as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn attempts_are_bound_to_their_quiz() {
+    let app = spawn_app().await;
+    let quiz = create_quiz(&app, "always").await;
+    let other = create_quiz(&app, "always").await;
+    let attempt_id = start_attempt(&app, &quiz.id).await;
+
+    // The attempt isn't reachable through another quiz
+    let response = app.api_client
+        .get(format!("{}/quizzes/{}/attempts/{}", &app.address, other.id, attempt_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(404, response.status().as_u16());
+
+    // Questions of another quiz can't be answered in this attempt
+    let (other_q, other_right, _) = &other.questions[0];
+    let response = app.api_client
+        .post(format!("{}/quizzes/{}/attempts/{}/answers", &app.address, quiz.id, attempt_id))
+        .json(&serde_json::json!({ "question_id": other_q, "option_id": other_right }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(404, response.status().as_u16());
+
+    // Unknown quiz
+    let response = app.api_client
+        .post(format!("{}/quizzes/{}/attempts", &app.address, coding_quiz_api::id::Id::new()))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(404, response.status().as_u16());
+}
+
+#[tokio::test]
+async fn time_limits_expire_attempts_and_questions() {
+    let app = spawn_app().await;
+    let common::QuizFixture { id: timed_quiz, questions: timed_questions, .. } = common::quiz("Timed Quiz")
+        .with("time_limit_seconds", serde_json::json!(1))
+        .question(serde_json::json!({ "text": "Q" }))
+        .create(&app)
+        .await;
+    let (timed_q, timed_right, _) = &timed_questions[0];
+    let common::QuizFixture { id: question_quiz, questions: limited_questions, .. } = common::quiz("Per-question Limit Quiz")
+        .question(serde_json::json!({ "text": "Q", "time_limit_seconds": 1 }))
+        .create(&app)
+        .await;
+    let (limited_q, limited_right, _) = &limited_questions[0];
+
+    let timed_attempt = start_attempt(&app, &timed_quiz).await;
+    let limited_attempt = start_attempt(&app, &question_quiz).await;
+    tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+
+    // The whole attempt ran out of time
+    let timed_url = format!("{}/quizzes/{}/attempts/{}", &app.address, timed_quiz, timed_attempt);
+    let response = app.api_client
+        .post(format!("{}/answers", &timed_url))
+        .json(&serde_json::json!({ "question_id": timed_q, "option_id": timed_right }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(409, response.status().as_u16());
+    let attempt: serde_json::Value = app.api_client.get(&timed_url).send().await.unwrap().json().await.unwrap();
+    assert_eq!(attempt["status"], "expired");
+    assert_eq!(attempt["finished_at"], attempt["expires_at"]);
+
+    // Only the question ran out of time: the late answer counts as wrong
+    let limited_url = format!("{}/quizzes/{}/attempts/{}", &app.address, question_quiz, limited_attempt);
+    let response = app.api_client
+        .post(format!("{}/answers", &limited_url))
+        .json(&serde_json::json!({ "question_id": limited_q, "option_id": limited_right }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(409, response.status().as_u16());
+    let result: serde_json::Value = app.api_client
+        .post(format!("{}/finish", &limited_url))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(result["breakdown"][0]["timed_out"], true);
+    assert_eq!(result["breakdown"][0]["correct"], false);
+    assert_eq!(result["score"], 0.0);
+}
+
+#[tokio::test]
+async fn exam_mode_hides_results_until_finished() {
+    let app = spawn_app().await;
+    let quiz = common::QuizFixture::from_json(common::post_quiz(&app, serde_json::json!({
+        "title": "Exam",
+        "exam_mode": true,
+        "questions": [{
+            "text": "Q",
+            "explanation": "Because.",
+            "options": [
+                { "text": "Right", "is_correct": true, "description": "Correct! This is the one." },
+                { "text": "Wrong", "is_correct": false, "description": "Not this one." }
+            ]
+        }],
+        "tags": []
+    })).await);
+    let (quiz_id, (question_id, right_id, _)) = (&quiz.id, &quiz.questions[0]);
+
+    // The answer key can't be read off the quiz before the attempt
+    let quiz: serde_json::Value = app.api_client
+        .get(format!("{}/quizzes/{}", &app.address, quiz_id))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert!(!quiz.to_string().contains("This is the one"));
+    assert!(!quiz.to_string().contains("Because."));
+
+    // Standalone solving would leak answers
+    let response = app.api_client
+        .post(format!("{}/quizzes/{}/solve", &app.address, quiz_id))
+        .json(&serde_json::json!({ "question_id": question_id, "option_id": right_id }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(403, response.status().as_u16());
+
+    let attempt_id = start_attempt(&app, quiz_id).await;
+    let attempt_url = format!("{}/quizzes/{}/attempts/{}", &app.address, quiz_id, attempt_id);
+    let questions = app.api_client.get(format!("{}/questions", &attempt_url)).send().await.unwrap().text().await.unwrap();
+    assert!(!questions.contains("This is the one"));
+    assert!(!questions.contains("Because."));
+    let answer: serde_json::Value = app.api_client
+        .post(format!("{}/answers", &attempt_url))
+        .json(&serde_json::json!({ "question_id": question_id, "option_id": right_id }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert!(answer.get("correct").is_none());
+    assert!(answer.get("explanation").is_none());
+    assert!(answer.get("score").is_none());
+    assert!(answer.get("option_feedback").is_none());
+
+    // Nor can it be read off the learner's progress before the attempt is finished
+    let progress = |app: &common::TestApp| app.api_client
+        .get(format!("{}/me/progress?user_email=learner@example.com", &app.address))
+        .send();
+    let report: serde_json::Value = progress(&app).await.unwrap().json().await.unwrap();
+    assert_eq!(report["total_answers"], 0);
+
+    let result: serde_json::Value = app.api_client
+        .post(format!("{}/finish", &attempt_url))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(result["breakdown"][0]["correct"], true);
+    assert_eq!(result["breakdown"][0]["explanation"], "Because.");
+    let questions = app.api_client.get(format!("{}/questions", &attempt_url)).send().await.unwrap().text().await.unwrap();
+    assert!(questions.contains("Because."));
+    let report: serde_json::Value = progress(&app).await.unwrap().json().await.unwrap();
+    assert_eq!(report["correct_answers"], 1);
+}
+
+#[tokio::test]
+async fn timed_out_exam_attempts_still_count() {
+    let app = spawn_app().await;
+    let quiz = common::quiz("Timed Exam")
+        .with("exam_mode", serde_json::json!(true))
+        .with("time_limit_seconds", serde_json::json!(1))
+        .questions(1)
+        .create(&app)
+        .await;
+    let (question_id, right_id, _) = &quiz.questions[0];
+    let attempt_id = start_attempt(&app, &quiz.id).await;
+    let attempt_url = format!("{}/quizzes/{}/attempts/{}", &app.address, quiz.id, attempt_id);
+    let response = app.api_client
+        .post(format!("{}/answers", &attempt_url))
+        .json(&serde_json::json!({ "question_id": question_id, "option_id": right_id }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(200, response.status().as_u16());
+    tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+
+    // Running out of time settles the attempt like finishing it would, once
+    let attempt: serde_json::Value = app.api_client.get(&attempt_url).send().await.unwrap().json().await.unwrap();
+    assert_eq!(attempt["status"], "expired");
+    let result: serde_json::Value = app.api_client
+        .post(format!("{}/finish", &attempt_url))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(result["breakdown"][0]["correct"], true);
+
+    let report: serde_json::Value = app.api_client
+        .get(format!("{}/me/progress?user_email=learner@example.com", &app.address))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(report["total_answers"], 1);
+    assert_eq!(report["correct_answers"], 1);
+    let achievements: serde_json::Value = app.api_client
+        .get(format!("{}/me/achievements?user_email=learner@example.com", &app.address))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert!(achievements["earned"].as_array().unwrap().iter().any(|b| b["id"] == "first_perfect_score"));
+}
+
+async fn option_order(app: &common::TestApp, quiz_id: &str, attempt_id: &str) -> Vec<String> {
+    let questions: serde_json::Value = app.api_client
+        .get(format!("{}/quizzes/{}/attempts/{}/questions", &app.address, quiz_id, attempt_id))
+        .send()
+        .await
+        .expect("Failed to fetch attempt questions")
+        .json()
+        .await
+        .unwrap();
+    questions.as_array().unwrap().iter()
+        .flat_map(|q| q["options"].as_array().unwrap().iter().map(|o| o["text"].as_str().unwrap().to_string()))
+        .collect()
+}
+
+#[tokio::test]
+async fn attempt_questions_are_shuffled_stably_per_attempt() {
+    let app = spawn_app().await;
+    let options: Vec<serde_json::Value> = (0..8)
+        .map(|i| match i {
+            0 => serde_json::json!({ "text": "Right", "is_correct": true }),
+            _ => serde_json::json!({ "text": format!("O{}", i), "is_correct": false }),
+        })
+        .collect();
+    let seed_order: Vec<String> = options.iter().map(|o| o["text"].as_str().unwrap().to_string()).collect();
+
+    let quiz_id = common::post_quiz(&app, serde_json::json!({
+        "title": "Shuffled",
+        "questions": [{ "text": "Q", "options": options }]
+    })).await["id"].as_str().unwrap().to_string();
+    let attempt_id = start_attempt(&app, &quiz_id).await;
+    let order = option_order(&app, &quiz_id, &attempt_id).await;
+    assert_eq!(order, option_order(&app, &quiz_id, &attempt_id).await);
+    let (mut sorted, mut expected) = (order.clone(), seed_order.clone());
+    sorted.sort();
+    expected.sort();
+    assert_eq!(sorted, expected);
+
+    let fixed_id = common::post_quiz(&app, serde_json::json!({
+        "title": "All of the above",
+        "shuffle_options": false,
+        "questions": [{ "text": "Q", "options": options }]
+    })).await["id"].as_str().unwrap().to_string();
+    let attempt_id = start_attempt(&app, &fixed_id).await;
+    assert_eq!(option_order(&app, &fixed_id, &attempt_id).await, seed_order);
+
+    // Attempts belong to their quiz
+    let response = app.api_client
+        .get(format!("{}/quizzes/{}/attempts/{}/questions", &app.address, quiz_id, attempt_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(404, response.status().as_u16());
+}
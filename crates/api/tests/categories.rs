@@ -14,7 +14,7 @@ async fn create_and_list_categories_works() {
     });
 
     let response = app.api_client
-        .post(&format!("{}/categories", &app.address))
+        .post(format!("{}/categories", &app.address))
         .json(&create_body)
         .send()
         .await
@@ -26,12 +26,258 @@ async fn create_and_list_categories_works() {
 
     // 2. List Categories
     let response = app.api_client
-        .get(&format!("{}/categories", &app.address))
+        .get(format!("{}/categories", &app.address))
         .send()
         .await
         .expect("Failed to execute request.");
 
     assert_eq!(200, response.status().as_u16());
-    let _list: Vec<serde_json::Value> = response.json().await.expect("Failed to parse JSON list");
+    let list: serde_json::Value = response.json().await.expect("Failed to parse JSON list");
+    assert_eq!(list["total"], 1);
+    assert_eq!(list["items"][0]["name"], category_name);
 }
 
+
+async fn create_category(app: &common::TestApp, name: &str) -> reqwest::Response {
+    app.api_client
+        .post(format!("{}/categories", &app.address))
+        .json(&serde_json::json!({ "name": name }))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+#[tokio::test]
+async fn category_get_update_and_duplicate_names() {
+    let app = spawn_app().await;
+    let category: serde_json::Value = create_category(&app, "Frontend").await.json().await.unwrap();
+    let id = category["id"].as_str().unwrap();
+    create_category(&app, "Backend").await;
+
+    let response = app.api_client
+        .get(format!("{}/categories/{}", &app.address, id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(200, response.status().as_u16());
+
+    assert_eq!(409, create_category(&app, " frontend ").await.status().as_u16());
+    assert_eq!(422, create_category(&app, "  ").await.status().as_u16());
+
+    let rename = |name: &'static str| {
+        let request = app.api_client
+            .put(format!("{}/categories/{}", &app.address, id))
+            .json(&serde_json::json!({ "name": name }));
+        async move { request.send().await.unwrap() }
+    };
+    assert_eq!(409, rename("BACKEND").await.status().as_u16());
+    let response = rename(" Web ").await;
+    assert_eq!(200, response.status().as_u16());
+    let renamed: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(renamed["name"], "Web");
+    // Changing only the case of its own name is fine
+    assert_eq!(200, rename("web").await.status().as_u16());
+
+    let response = app.api_client
+        .get(format!("{}/categories/{}", &app.address, coding_quiz_api::id::Id::new()))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(404, response.status().as_u16());
+}
+
+#[tokio::test]
+async fn quizzes_must_reference_existing_categories() {
+    let app = spawn_app().await;
+    let category: serde_json::Value = create_category(&app, "Rust").await.json().await.unwrap();
+    let category_id = category["id"].as_str().unwrap();
+    let unknown_id = coding_quiz_api::id::Id::new().to_string();
+
+    let response = app.api_client
+        .post(format!("{}/quizzes", &app.address))
+        .json(&serde_json::json!({ "title": "Orphan", "questions": [], "category_id": unknown_id }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(400, response.status().as_u16());
+
+    let quiz: serde_json::Value = app.api_client
+        .post(format!("{}/quizzes", &app.address))
+        .json(&serde_json::json!({ "title": "Ownership", "questions": [], "category_id": category_id }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let quiz_id = quiz["id"].as_str().unwrap();
+
+    let response = app.api_client
+        .put(format!("{}/quizzes/{}", &app.address, quiz_id))
+        .json(&serde_json::json!({ "category_id": unknown_id }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(400, response.status().as_u16());
+
+    // Deleting the category detaches its quizzes
+    let response = app.api_client
+        .delete(format!("{}/categories/{}", &app.address, category_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(204, response.status().as_u16());
+
+    let quiz: serde_json::Value = app.api_client
+        .get(format!("{}/quizzes/{}", &app.address, quiz_id))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(quiz["category_id"], serde_json::Value::Null);
+
+    let response = app.api_client
+        .delete(format!("{}/categories/{}", &app.address, category_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(404, response.status().as_u16());
+}
+
+async fn create_child(app: &common::TestApp, name: &str, parent_id: Option<&str>) -> String {
+    let category: serde_json::Value = app.api_client
+        .post(format!("{}/categories", &app.address))
+        .json(&serde_json::json!({ "name": name, "parent_id": parent_id }))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json()
+        .await
+        .unwrap();
+    category["id"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn categories_form_a_tree() {
+    let app = spawn_app().await;
+    let js = create_child(&app, "JavaScript", None).await;
+    let async_ = create_child(&app, "Async", Some(&js)).await;
+    let promises = create_child(&app, "Promises", Some(&async_)).await;
+    let rust = create_child(&app, "Rust", None).await;
+    // Names only need to be unique among siblings
+    let rust_async = create_child(&app, "Async", Some(&rust)).await;
+    assert_ne!(rust_async, async_);
+
+    let tree: serde_json::Value = app.api_client
+        .get(format!("{}/categories/tree", &app.address))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(tree[0]["name"], "JavaScript");
+    assert_eq!(tree[0]["children"][0]["children"][0]["name"], "Promises");
+    assert_eq!(tree[1]["children"][0]["id"], rust_async.as_str());
+
+    let path: serde_json::Value = app.api_client
+        .get(format!("{}/categories/{}/path", &app.address, promises))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let names: Vec<&str> = path.as_array().unwrap().iter().map(|c| c["name"].as_str().unwrap()).collect();
+    assert_eq!(names, ["JavaScript", "Async", "Promises"]);
+
+    // A category cannot move below its own descendant
+    let response = app.api_client
+        .put(format!("{}/categories/{}", &app.address, js))
+        .json(&serde_json::json!({ "parent_id": promises }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(400, response.status().as_u16());
+
+    for (title, category) in [("Callbacks", &js), ("Then chains", &promises), ("Tokio", &rust_async)] {
+        app.api_client
+            .post(format!("{}/quizzes", &app.address))
+            .json(&serde_json::json!({ "title": title, "questions": [], "category_id": category }))
+            .send()
+            .await
+            .unwrap();
+    }
+    let list = |query: String| {
+        let request = app.api_client.get(format!("{}/quizzes?{}", &app.address, query));
+        async move {
+            let page: serde_json::Value = request.send().await.unwrap().json().await.unwrap();
+            page["items"].as_array().unwrap().iter().map(|q| q["title"].as_str().unwrap().to_string()).collect::<Vec<_>>()
+        }
+    };
+    assert_eq!(list(format!("category_id={}", js)).await, ["Callbacks"]);
+    assert_eq!(list(format!("category_id={}&include_descendants=true", js)).await, ["Callbacks", "Then chains"]);
+
+    // Deleting a category moves its children up; moving to the top level takes an explicit null
+    app.api_client
+        .delete(format!("{}/categories/{}", &app.address, async_))
+        .send()
+        .await
+        .unwrap();
+    let moved: serde_json::Value = app.api_client
+        .get(format!("{}/categories/{}", &app.address, promises))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(moved["parent_id"], js.as_str());
+
+    let moved: serde_json::Value = app.api_client
+        .put(format!("{}/categories/{}", &app.address, promises))
+        .json(&serde_json::json!({ "parent_id": null }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(moved["parent_id"], serde_json::Value::Null);
+    assert_eq!(moved["name"], "Promises");
+}
+
+#[tokio::test]
+async fn deleting_a_category_keeps_sibling_names_unique() {
+    let app = spawn_app().await;
+    let js = create_child(&app, "JavaScript", None).await;
+    create_child(&app, "DOM", Some(&js)).await;
+    let web = create_child(&app, "Web", Some(&js)).await;
+    create_child(&app, "dom", Some(&web)).await;
+
+    // Web's "dom" would land next to "DOM"
+    let response = app.api_client
+        .delete(format!("{}/categories/{}", &app.address, web))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(409, response.status().as_u16());
+    let response = app.api_client
+        .get(format!("{}/categories/{}", &app.address, web))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(200, response.status().as_u16());
+
+    // A child may take the name of the category it replaces
+    let events = create_child(&app, "Events", Some(&js)).await;
+    create_child(&app, "Events", Some(&events)).await;
+    let response = app.api_client
+        .delete(format!("{}/categories/{}", &app.address, events))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(204, response.status().as_u16());
+}
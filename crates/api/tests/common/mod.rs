@@ -1,7 +1,8 @@
+#![allow(dead_code)]
+
 use coding_quiz_api::run;
 use std::net::TcpListener;
 
-#[allow(dead_code)]
 pub struct TestApp {
     pub address: String,
     pub api_client: reqwest::Client,
@@ -13,10 +14,83 @@ pub async fn spawn_app() -> TestApp {
     let address = format!("http://127.0.0.1:{}", port);
 
     let server = run(listener, vec![], vec![]).expect("Failed to bind address");
-    let _ = tokio::spawn(server);
+    tokio::spawn(server);
 
     TestApp {
         address,
         api_client: reqwest::Client::new(),
     }
 }
+
+/// Creates a quiz from a raw body and returns the stored quiz.
+pub async fn post_quiz(app: &TestApp, body: serde_json::Value) -> serde_json::Value {
+    let response = app.api_client
+        .post(format!("{}/quizzes", &app.address))
+        .json(&body)
+        .send()
+        .await
+        .expect("Failed to create quiz");
+    assert_eq!(201, response.status().as_u16());
+    response.json().await.unwrap()
+}
+
+/// A created quiz, with (question_id, right_option_id, wrong_option_id) per question.
+pub struct QuizFixture {
+    pub id: String,
+    pub questions: Vec<(String, String, String)>,
+    pub json: serde_json::Value,
+}
+
+impl QuizFixture {
+    /// Reads the IDs from a quiz whose options are labelled "Right" and "Wrong",
+    /// such as one built by [`QuizBuilder`] or generated from those.
+    pub fn from_json(json: serde_json::Value) -> Self {
+        let questions = json["questions"].as_array().unwrap().iter().map(|q| {
+            let option_id = |text: &str| q["options"].as_array().unwrap().iter()
+                .find(|o| o["text"] == text).unwrap()["id"].as_str().unwrap().to_string();
+            (q["id"].as_str().unwrap().to_string(), option_id("Right"), option_id("Wrong"))
+        }).collect();
+        QuizFixture { id: json["id"].as_str().unwrap().to_string(), questions, json }
+    }
+}
+
+/// Builds a quiz whose questions each have one "Right" and one "Wrong" option.
+pub struct QuizBuilder {
+    body: serde_json::Value,
+}
+
+pub fn quiz(title: &str) -> QuizBuilder {
+    QuizBuilder { body: serde_json::json!({ "title": title, "tags": [], "questions": [] }) }
+}
+
+impl QuizBuilder {
+    /// Sets a top-level field such as `tags`, `category_id` or `reveal_policy`.
+    pub fn with(mut self, field: &str, value: serde_json::Value) -> Self {
+        self.body[field] = value;
+        self
+    }
+
+    pub fn tags(self, tags: &[&str]) -> Self {
+        self.with("tags", serde_json::json!(tags))
+    }
+
+    /// Adds a question; `fields` may set anything but the options, e.g. `{ "text": "Q", "hints": [...] }`.
+    pub fn question(mut self, mut fields: serde_json::Value) -> Self {
+        fields["options"] = serde_json::json!([
+            { "text": "Right", "is_correct": true },
+            { "text": "Wrong", "is_correct": false }
+        ]);
+        self.body["questions"].as_array_mut().unwrap().push(fields);
+        self
+    }
+
+    /// Adds `count` questions titled "<quiz title> Q<n>".
+    pub fn questions(self, count: usize) -> Self {
+        let title = self.body["title"].as_str().unwrap().to_string();
+        (0..count).fold(self, |builder, i| builder.question(serde_json::json!({ "text": format!("{} Q{}", title, i) })))
+    }
+
+    pub async fn create(self, app: &TestApp) -> QuizFixture {
+        QuizFixture::from_json(post_quiz(app, self.body).await)
+    }
+}
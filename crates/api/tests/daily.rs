@@ -0,0 +1,136 @@
+use crate::common::spawn_app;
+
+mod common;
+
+async fn get(app: &common::TestApp, path: &str) -> (u16, serde_json::Value) {
+    let response = app.api_client
+        .get(format!("{}{}", &app.address, path))
+        .send()
+        .await
+        .expect("Failed to execute request");
+    let status = response.status().as_u16();
+    (status, response.json().await.unwrap())
+}
+
+async fn start_daily(app: &common::TestApp, user_email: &str) -> reqwest::Response {
+    app.api_client
+        .post(format!("{}/daily/attempts", &app.address))
+        .json(&serde_json::json!({ "user_email": user_email }))
+        .send()
+        .await
+        .expect("Failed to start daily attempt")
+}
+
+#[tokio::test]
+async fn daily_challenge_is_stable_for_the_day() {
+    let app = spawn_app().await;
+    let (status, _) = get(&app, "/daily").await;
+    assert_eq!(404, status);
+
+    common::quiz("Pool").tags(&["pool"]).questions(8).create(&app).await;
+    let (status, first) = get(&app, "/daily").await;
+    assert_eq!(200, status);
+    assert_eq!(first["quiz"]["questions"].as_array().unwrap().len(), 5);
+    assert_eq!(first["quiz"]["generated"], true);
+    assert_eq!(first["quiz"]["tags"], serde_json::json!(["pool"]));
+
+    let (_, second) = get(&app, "/daily?tz=UTC").await;
+    assert_eq!(first["quiz"]["id"], second["quiz"]["id"]);
+    assert_eq!(first["date"], second["date"]);
+
+    // Far-apart offsets land on different calendar days
+    let (_, east) = get(&app, "/daily?tz=%2B14:00").await;
+    let (_, west) = get(&app, "/daily?tz=-12:00").await;
+    assert_ne!(east["date"], west["date"]);
+
+    let (status, _) = get(&app, "/daily?tz=Mars/Olympus").await;
+    assert_eq!(400, status);
+}
+
+#[tokio::test]
+async fn daily_challenge_allows_one_attempt_and_tracks_streaks() {
+    let app = spawn_app().await;
+    common::quiz("Pool").tags(&["pool"]).questions(3).create(&app).await;
+    let user = "learner@example.com";
+
+    let response = start_daily(&app, user).await;
+    assert_eq!(201, response.status().as_u16());
+    let attempt: serde_json::Value = response.json().await.unwrap();
+    let quiz_id = attempt["quiz_id"].as_str().unwrap();
+    let attempt_id = attempt["id"].as_str().unwrap();
+
+    assert_eq!(409, start_daily(&app, "Learner@Example.com").await.status().as_u16());
+    let response = app.api_client
+        .post(format!("{}/quizzes/{}/attempts", &app.address, quiz_id))
+        .json(&serde_json::json!({ "user_email": user }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(403, response.status().as_u16());
+
+    let (_, challenge) = get(&app, &format!("/daily?user_email={}", user)).await;
+    assert_eq!(challenge["attempt"]["id"], attempt_id);
+    let (_, presented) = get(&app, &format!("/quizzes/{}/attempts/{}/questions", quiz_id, attempt_id)).await;
+    assert_eq!(challenge["quiz"]["questions"], presented);
+
+    let (_, streak) = get(&app, &format!("/daily/streak?user_email={}", user)).await;
+    assert_eq!(streak["current"], 0);
+    assert_eq!(streak["completed_today"], false);
+
+    let response = app.api_client
+        .post(format!("{}/quizzes/{}/attempts/{}/finish", &app.address, quiz_id, attempt_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(200, response.status().as_u16());
+
+    let (_, streak) = get(&app, &format!("/daily/streak?user_email={}", user)).await;
+    assert_eq!(streak["current"], 1);
+    assert_eq!(streak["longest"], 1);
+    assert_eq!(streak["completed_today"], true);
+
+    let (status, _) = get(&app, "/daily/streak").await;
+    assert_eq!(400, status);
+}
+
+#[tokio::test]
+async fn first_daily_attempt_pins_the_learners_offset() {
+    let app = spawn_app().await;
+    common::quiz("Pool").tags(&["pool"]).questions(3).create(&app).await;
+    let user = "learner@example.com";
+
+    let response = app.api_client
+        .post(format!("{}/daily/attempts", &app.address))
+        .json(&serde_json::json!({ "user_email": user, "tz": "+14:00" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(201, response.status().as_u16());
+    let (_, east) = get(&app, "/daily?tz=%2B14:00").await;
+
+    // Switching to an offset on another calendar day would grant a second attempt
+    let response = app.api_client
+        .post(format!("{}/daily/attempts", &app.address))
+        .json(&serde_json::json!({ "user_email": user, "tz": "-12:00" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(409, response.status().as_u16());
+    let json: serde_json::Value = response.json().await.unwrap();
+    assert!(json["error"].as_str().unwrap().contains("+14:00"));
+
+    let (status, _) = get(&app, &format!("/daily?user_email={}&tz=-12:00", user)).await;
+    assert_eq!(409, status);
+    let (status, _) = get(&app, &format!("/daily/streak?user_email={}&tz=-12:00", user)).await;
+    assert_eq!(409, status);
+
+    // Without tz the pinned offset decides the day
+    let (status, mine) = get(&app, &format!("/daily?user_email={}", user)).await;
+    assert_eq!(200, status);
+    assert_eq!(mine["date"], east["date"]);
+    assert!(mine["attempt"].is_object());
+
+    // Other learners are unaffected
+    let (status, _) = get(&app, "/daily?user_email=other@example.com&tz=-12:00").await;
+    assert_eq!(200, status);
+}
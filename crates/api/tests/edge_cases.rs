@@ -1,11 +1,9 @@
 use crate::common::spawn_app;
-use coding_quiz_api::id::Id;
-use uuid::Uuid;
 
 mod common;
 
 #[tokio::test]
-async fn invalid_json_returns_400() {
+async fn invalid_json_returns_422() {
     let app = spawn_app().await;
 
     // Missing 'title' field
@@ -15,13 +13,13 @@ async fn invalid_json_returns_400() {
     });
 
     let response = app.api_client
-        .post(&format!("{}/quizzes", &app.address))
+        .post(format!("{}/quizzes", &app.address))
         .json(&body)
         .send()
         .await
         .expect("Failed to execute request.");
 
-    assert_eq!(400, response.status().as_u16());
+    assert_eq!(422, response.status().as_u16());
 }
 
 // ===== Edge Cases =====
@@ -37,7 +35,7 @@ async fn create_quiz_with_empty_questions_succeeds() {
     });
 
     let response = app.api_client
-        .post(&format!("{}/quizzes", &app.address))
+        .post(format!("{}/quizzes", &app.address))
         .json(&body)
         .send()
         .await
@@ -58,14 +56,15 @@ async fn create_quiz_with_very_long_title() {
     });
 
     let response = app.api_client
-        .post(&format!("{}/quizzes", &app.address))
+        .post(format!("{}/quizzes", &app.address))
         .json(&body)
         .send()
         .await
         .expect("Failed to execute request.");
 
-    // Should succeed (or fail with validation if you add length limits)
-    assert!(response.status().as_u16() == 201 || response.status().as_u16() == 400);
+    assert_eq!(422, response.status().as_u16());
+    let json: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(json["fields"][0]["field"], "title");
 }
 
 #[tokio::test]
@@ -80,7 +79,7 @@ async fn create_quiz_with_special_characters_in_title() {
     });
 
     let response = app.api_client
-        .post(&format!("{}/quizzes", &app.address))
+        .post(format!("{}/quizzes", &app.address))
         .json(&body)
         .send()
         .await
@@ -110,7 +109,7 @@ async fn create_question_with_multiple_correct_answers() {
     });
 
     let response = app.api_client
-        .post(&format!("{}/quizzes", &app.address))
+        .post(format!("{}/quizzes", &app.address))
         .json(&body)
         .send()
         .await
@@ -137,14 +136,16 @@ async fn create_question_with_no_correct_answer() {
     });
 
     let response = app.api_client
-        .post(&format!("{}/quizzes", &app.address))
+        .post(format!("{}/quizzes", &app.address))
         .json(&body)
         .send()
         .await
         .expect("Failed to execute request.");
 
-    // Should succeed (validation could be added to require at least one correct answer)
-    assert_eq!(201, response.status().as_u16());
+    assert_eq!(422, response.status().as_u16());
+    let json: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(json["fields"][0]["field"], "questions[0].options");
+    assert_eq!(json["fields"][0]["message"], "must have at least one correct option");
 }
 
 // ===== Pagination Tests =====
@@ -161,7 +162,7 @@ async fn pagination_first_page_works() {
             "tags": []
         });
         app.api_client
-            .post(&format!("{}/quizzes", &app.address))
+            .post(format!("{}/quizzes", &app.address))
             .json(&body)
             .send()
             .await
@@ -170,7 +171,7 @@ async fn pagination_first_page_works() {
 
     // Get first page with limit 2
     let response = app.api_client
-        .get(&format!("{}/quizzes", &app.address))
+        .get(format!("{}/quizzes", &app.address))
         .query(&[("per_page", "2"), ("page", "1")])
         .send()
         .await
@@ -178,7 +179,7 @@ async fn pagination_first_page_works() {
 
     assert_eq!(200, response.status().as_u16());
     let json: serde_json::Value = response.json().await.unwrap();
-    let quizzes = json.as_array().unwrap();
+    let quizzes = json["items"].as_array().unwrap();
     assert!(quizzes.len() <= 2);
 }
 
@@ -188,7 +189,7 @@ async fn pagination_beyond_available_pages_returns_empty() {
 
     // Request page 9999 (likely beyond available data)
     let response = app.api_client
-        .get(&format!("{}/quizzes", &app.address))
+        .get(format!("{}/quizzes", &app.address))
         .query(&[("per_page", "10"), ("page", "9999")])
         .send()
         .await
@@ -196,9 +197,9 @@ async fn pagination_beyond_available_pages_returns_empty() {
 
     assert_eq!(200, response.status().as_u16());
     let json: serde_json::Value = response.json().await.unwrap();
-    let quizzes = json.as_array().unwrap();
-    // Should return empty array or small number
-    assert!(quizzes.len() <= 10);
+    let quizzes = json["items"].as_array().unwrap();
+    assert!(quizzes.is_empty());
+    assert_eq!(json["next"], serde_json::Value::Null);
 }
 
 // ===== Concurrent Operations =====
@@ -218,7 +219,7 @@ async fn concurrent_quiz_creation_works() {
                     "tags": []
                 });
                 client
-                    .post(&format!("{}/quizzes", &addr))
+                    .post(format!("{}/quizzes", &addr))
                     .json(&body)
                     .send()
                     .await
@@ -244,7 +245,7 @@ async fn malformed_json_returns_400() {
     let app = spawn_app().await;
 
     let response = app.api_client
-        .post(&format!("{}/quizzes", &app.address))
+        .post(format!("{}/quizzes", &app.address))
         .header("Content-Type", "application/json")
         .body("{invalid json}")
         .send()
@@ -255,7 +256,7 @@ async fn malformed_json_returns_400() {
 }
 
 #[tokio::test]
-async fn missing_required_fields_returns_400() {
+async fn missing_required_fields_returns_422() {
     let app = spawn_app().await;
 
     let body = serde_json::json!({
@@ -264,13 +265,15 @@ async fn missing_required_fields_returns_400() {
     });
 
     let response = app.api_client
-        .post(&format!("{}/quizzes", &app.address))
+        .post(format!("{}/quizzes", &app.address))
         .json(&body)
         .send()
         .await
         .expect("Failed to execute request.");
 
-    assert_eq!(400, response.status().as_u16());
+    assert_eq!(422, response.status().as_u16());
+    let json: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(json["fields"][0]["field"], "title");
 }
 
 #[tokio::test]
@@ -278,7 +281,7 @@ async fn invalid_uuid_format_returns_400_or_404() {
     let app = spawn_app().await;
 
     let response = app.api_client
-        .get(&format!("{}/quizzes/not-a-valid-uuid", &app.address))
+        .get(format!("{}/quizzes/not-a-valid-uuid", &app.address))
         .send()
         .await
         .expect("Failed to execute request.");
@@ -300,7 +303,7 @@ async fn quiz_with_duplicate_tags_deduplicates() {
     });
 
     let response = app.api_client
-        .post(&format!("{}/quizzes", &app.address))
+        .post(format!("{}/quizzes", &app.address))
         .json(&body)
         .send()
         .await
@@ -308,10 +311,7 @@ async fn quiz_with_duplicate_tags_deduplicates() {
 
     assert_eq!(201, response.status().as_u16());
     let json: serde_json::Value = response.json().await.unwrap();
-    let tags = json["tags"].as_array().unwrap();
-    
-    let rust_count = tags.iter().filter(|t| t.as_str().unwrap() == "rust").count();
-    assert!(rust_count >= 1); 
+    assert_eq!(json["tags"], serde_json::json!(["rust", "programming"]));
 }
 
 #[tokio::test]
@@ -326,7 +326,7 @@ async fn update_quiz_tags_replaces_old_tags() {
     });
 
     let response = app.api_client
-        .post(&format!("{}/quizzes", &app.address))
+        .post(format!("{}/quizzes", &app.address))
         .json(&body)
         .send()
         .await
@@ -341,7 +341,7 @@ async fn update_quiz_tags_replaces_old_tags() {
     });
 
     let response = app.api_client
-        .put(&format!("{}/quizzes/{}", &app.address, quiz_id))
+        .put(format!("{}/quizzes/{}", &app.address, quiz_id))
         .json(&update_body)
         .send()
         .await
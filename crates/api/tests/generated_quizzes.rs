@@ -0,0 +1,177 @@
+use crate::common::spawn_app;
+use std::collections::HashSet;
+
+mod common;
+
+#[tokio::test]
+async fn random_question_is_drawn_from_matching_quizzes() {
+    let app = spawn_app().await;
+    let pool_quiz = common::quiz("Pool").tags(&["pool"]).questions(5).create(&app).await;
+    common::quiz("Other").tags(&["other"]).questions(3).create(&app).await;
+
+    for seed in 0..5 {
+        let response = app.api_client
+            .get(format!("{}/questions/random", &app.address))
+            .query(&[("tags", "pool"), ("seed", &seed.to_string())])
+            .send()
+            .await
+            .expect("Failed to get random question");
+        assert_eq!(200, response.status().as_u16());
+        let json: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(json["quiz_id"], pool_quiz.id);
+        assert!(json["question"]["text"].as_str().unwrap().starts_with("Pool"));
+    }
+
+    let response = app.api_client
+        .get(format!("{}/questions/random", &app.address))
+        .query(&[("tags", "missing")])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(404, response.status().as_u16());
+}
+
+#[tokio::test]
+async fn generated_quiz_has_distinct_questions_and_can_be_attempted() {
+    let app = spawn_app().await;
+    common::quiz("Pool").tags(&["pool"]).questions(5).create(&app).await;
+    common::quiz("Other").tags(&["other"]).questions(3).create(&app).await;
+
+    let response = app.api_client
+        .post(format!("{}/quizzes/generate", &app.address))
+        .json(&serde_json::json!({ "count": 3, "tags": ["pool"], "seed": 1 }))
+        .send()
+        .await
+        .expect("Failed to generate quiz");
+    assert_eq!(201, response.status().as_u16());
+    let generated: serde_json::Value = response.json().await.unwrap();
+    let generated_id = generated["id"].as_str().unwrap();
+    assert_eq!(generated["generated"], true);
+    assert_eq!(generated["tags"], serde_json::json!(["pool"]));
+
+    let questions = generated["questions"].as_array().unwrap();
+    assert_eq!(questions.len(), 3);
+    let ids: HashSet<&str> = questions.iter().map(|q| q["id"].as_str().unwrap()).collect();
+    assert_eq!(ids.len(), 3);
+
+    // Re-fetchable by ID, but not listed alongside authored quizzes
+    let response = app.api_client
+        .get(format!("{}/quizzes/{}", &app.address, generated_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(200, response.status().as_u16());
+    let listed: serde_json::Value = app.api_client
+        .get(format!("{}/quizzes", &app.address))
+        .query(&[("per_page", "100")])
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert!(!listed["items"].as_array().unwrap().iter().any(|q| q["id"] == generated["id"]));
+
+    // Attempts and solving work against the generated quiz
+    let question = &questions[0];
+    let right_id = question["options"].as_array().unwrap().iter()
+        .find(|o| o["text"] == "Right").unwrap()["id"].clone();
+    let answer: serde_json::Value = app.api_client
+        .post(format!("{}/quizzes/{}/solve", &app.address, generated_id))
+        .json(&serde_json::json!({ "question_id": question["id"], "option_id": right_id }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(answer["correct"], true);
+
+    let response = app.api_client
+        .post(format!("{}/quizzes/{}/attempts", &app.address, generated_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(201, response.status().as_u16());
+}
+
+#[tokio::test]
+async fn generate_quiz_caps_to_pool_and_validates_count() {
+    let app = spawn_app().await;
+    common::quiz("Pool").tags(&["pool"]).questions(5).create(&app).await;
+
+    let generated: serde_json::Value = app.api_client
+        .post(format!("{}/quizzes/generate", &app.address))
+        .json(&serde_json::json!({ "count": 50, "tags": ["pool"] }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(generated["questions"].as_array().unwrap().len(), 5);
+
+    let response = app.api_client
+        .post(format!("{}/quizzes/generate", &app.address))
+        .json(&serde_json::json!({ "count": 0 }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(422, response.status().as_u16());
+
+    let response = app.api_client
+        .post(format!("{}/quizzes/generate", &app.address))
+        .json(&serde_json::json!({ "count": 3, "tags": ["missing"] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(404, response.status().as_u16());
+}
+
+#[tokio::test]
+async fn exam_and_withheld_answers_stay_out_of_the_pool() {
+    let app = spawn_app().await;
+    for (field, value) in [("exam_mode", serde_json::json!(true)), ("reveal_policy", serde_json::json!("never"))] {
+        common::quiz("Locked")
+            .tags(&["locked"])
+            .with(field, value)
+            .question(serde_json::json!({ "text": "Secret?" }))
+            .create(&app)
+            .await;
+    }
+
+    let response = app.api_client
+        .post(format!("{}/quizzes/generate", &app.address))
+        .json(&serde_json::json!({ "count": 5, "tags": ["locked"] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(404, response.status().as_u16());
+}
+
+#[tokio::test]
+async fn oldest_generated_quizzes_are_dropped_beyond_the_cap() {
+    let app = spawn_app().await;
+    common::quiz("Pool").tags(&["pool"]).questions(3).create(&app).await;
+
+    let mut ids = Vec::new();
+    for _ in 0..=coding_quiz_api::state::MAX_GENERATED_QUIZZES {
+        let generated: serde_json::Value = app.api_client
+            .post(format!("{}/quizzes/generate", &app.address))
+            .json(&serde_json::json!({ "count": 1 }))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        ids.push(generated["id"].as_str().unwrap().to_string());
+    }
+
+    let status = |id: String| {
+        let request = app.api_client.get(format!("{}/quizzes/{}", &app.address, id));
+        async move { request.send().await.unwrap().status().as_u16() }
+    };
+    assert_eq!(404, status(ids[0].clone()).await);
+    assert_eq!(200, status(ids[1].clone()).await);
+}
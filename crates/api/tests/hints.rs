@@ -0,0 +1,132 @@
+use crate::common::spawn_app;
+
+mod common;
+
+async fn create_hinted_quiz(app: &common::TestApp) -> (String, String, String) {
+    let quiz = common::quiz("Hinted Quiz")
+        .question(serde_json::json!({
+            "text": "Which keyword declares a block-scoped constant?",
+            "explanation": "const declares a block-scoped constant binding.",
+            "hints": ["It was added in ES6.", "It is not `let`."]
+        }))
+        .create(app)
+        .await;
+    let (question_id, correct_id, _) = quiz.questions[0].clone();
+    (quiz.id, question_id, correct_id)
+}
+
+#[tokio::test]
+async fn hints_are_revealed_in_order_and_reduce_score() {
+    let app = spawn_app().await;
+    let (quiz_id, question_id, correct_id) = create_hinted_quiz(&app).await;
+    let hint_url = format!("{}/quizzes/{}/questions/{}/hints/next", &app.address, quiz_id, question_id);
+
+    // Hints are not part of the public quiz document
+    let quiz: serde_json::Value = app.api_client
+        .get(format!("{}/quizzes/{}", &app.address, quiz_id))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert!(quiz["questions"][0].get("hints").is_none());
+
+    let first: serde_json::Value = app.api_client
+        .get(&hint_url)
+        .query(&[("user_email", "learner@example.com")])
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(first["hint"], "It was added in ES6.");
+    assert_eq!(first["hint_number"], 1);
+    assert_eq!(first["remaining"], 1);
+
+    let second: serde_json::Value = app.api_client
+        .get(&hint_url)
+        .query(&[("user_email", "Learner@Example.com")])
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(second["hint"], "It is not `let`.");
+    assert_eq!(second["penalty"], 0.5);
+
+    let response = app.api_client
+        .get(&hint_url)
+        .query(&[("user_email", "learner@example.com")])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(404, response.status().as_u16());
+
+    let answer: serde_json::Value = app.api_client
+        .post(format!("{}/quizzes/{}/solve", &app.address, quiz_id))
+        .json(&serde_json::json!({
+            "question_id": question_id,
+            "option_id": correct_id,
+            "user_email": "learner@example.com"
+        }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(answer["correct"], true);
+    assert_eq!(answer["hints_used"], 2);
+    assert_eq!(answer["score"], 0.5);
+
+    // The penalty was charged to that answer; the next one starts clean
+    let answer: serde_json::Value = app.api_client
+        .post(format!("{}/quizzes/{}/solve", &app.address, quiz_id))
+        .json(&serde_json::json!({
+            "question_id": question_id,
+            "option_id": correct_id,
+            "user_email": "learner@example.com"
+        }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(answer["hints_used"], 0);
+    assert_eq!(answer["score"], 1.0);
+
+    let again: serde_json::Value = app.api_client
+        .get(&hint_url)
+        .query(&[("user_email", "learner@example.com")])
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(again["hint_number"], 1);
+}
+
+#[tokio::test]
+async fn hint_usage_is_tracked_per_user() {
+    let app = spawn_app().await;
+    let (quiz_id, question_id, _) = create_hinted_quiz(&app).await;
+    let hint_url = format!("{}/quizzes/{}/questions/{}/hints/next", &app.address, quiz_id, question_id);
+
+    for email in ["a@example.com", "b@example.com"] {
+        let hint: serde_json::Value = app.api_client
+            .get(&hint_url)
+            .query(&[("user_email", email)])
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(hint["hint_number"], 1);
+    }
+}
@@ -0,0 +1,160 @@
+use crate::common::spawn_app;
+
+mod common;
+
+/// Runs a full attempt, answering the first `correct` questions right and the rest wrong.
+async fn play(app: &common::TestApp, quiz: &common::QuizFixture, user_email: &str, correct: usize) {
+    let common::QuizFixture { id: quiz_id, questions, .. } = quiz;
+    let attempt: serde_json::Value = app.api_client
+        .post(format!("{}/quizzes/{}/attempts", &app.address, quiz_id))
+        .json(&serde_json::json!({ "user_email": user_email }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let attempt_id = attempt["id"].as_str().unwrap();
+
+    for (i, (question_id, right, wrong)) in questions.iter().enumerate() {
+        let option_id = if i < correct { right } else { wrong };
+        app.api_client
+            .post(format!("{}/quizzes/{}/attempts/{}/answers", &app.address, quiz_id, attempt_id))
+            .json(&serde_json::json!({ "question_id": question_id, "option_id": option_id }))
+            .send()
+            .await
+            .unwrap();
+    }
+    app.api_client
+        .post(format!("{}/quizzes/{}/attempts/{}/finish", &app.address, quiz_id, attempt_id))
+        .send()
+        .await
+        .unwrap();
+}
+
+async fn get(app: &common::TestApp, path: &str) -> (u16, serde_json::Value) {
+    let response = app.api_client
+        .get(format!("{}{}", &app.address, path))
+        .send()
+        .await
+        .expect("Failed to fetch leaderboard");
+    let status = response.status().as_u16();
+    (status, response.json().await.unwrap())
+}
+
+fn users(board: &serde_json::Value) -> Vec<&str> {
+    board["entries"].as_array().unwrap().iter().map(|e| e["user_email"].as_str().unwrap()).collect()
+}
+
+#[tokio::test]
+async fn leaderboards_rank_per_quiz_tag_and_globally() {
+    let app = spawn_app().await;
+    let async_quiz = common::quiz("async").tags(&["async"]).questions(2).create(&app).await;
+    let dom_quiz = common::quiz("dom").tags(&["dom"]).questions(2).create(&app).await;
+
+    play(&app, &async_quiz, "alice@example.com", 2).await;
+    play(&app, &async_quiz, "Bob@example.com", 1).await;
+    play(&app, &async_quiz, "carol@example.com", 0).await;
+    // Only the best attempt per quiz counts
+    play(&app, &async_quiz, "carol@example.com", 0).await;
+    play(&app, &dom_quiz, "bob@example.com", 2).await;
+
+    let (status, board) = get(&app, &format!("/leaderboards?quiz_id={}", async_quiz.id)).await;
+    assert_eq!(200, status);
+    assert_eq!(users(&board), ["a***@example.com", "b***@example.com", "c***@example.com"]);
+    assert_eq!(board["total"], 3);
+
+    let (_, board) = get(&app, "/leaderboards?tag=dom").await;
+    assert_eq!(users(&board), ["b***@example.com"]);
+
+    let (_, board) = get(&app, "/leaderboards?window=daily").await;
+    assert_eq!(users(&board), ["b***@example.com", "a***@example.com", "c***@example.com"]);
+    assert_eq!(board["entries"][0]["score"], 3.0);
+    assert_eq!(board["entries"][0]["quizzes"], 2);
+
+    let (_, board) = get(&app, "/leaderboards?per_page=1&page=2").await;
+    assert_eq!(users(&board), ["a***@example.com"]);
+    assert_eq!(board["total"], 3);
+
+    let (status, board) = get(&app, &format!("/leaderboards?page={}", u64::MAX)).await;
+    assert_eq!(200, status);
+    assert!(users(&board).is_empty());
+}
+
+#[tokio::test]
+async fn learners_can_find_their_own_rank() {
+    let app = spawn_app().await;
+    let quiz = common::quiz("async").tags(&["async"]).questions(2).create(&app).await;
+    play(&app, &quiz, "alice@example.com", 2).await;
+    play(&app, &quiz, "carol@example.com", 1).await;
+
+    let (status, me) = get(&app, "/leaderboards/me?user_email=Carol@Example.com").await;
+    assert_eq!(200, status);
+    assert_eq!(me["rank"], 2);
+    assert_eq!(me["score"], 1.0);
+    assert_eq!(me["user_email"], "c***@example.com");
+
+    let (status, _) = get(&app, "/leaderboards/me?user_email=nobody@example.com").await;
+    assert_eq!(404, status);
+    let (status, _) = get(&app, "/leaderboards/me").await;
+    assert_eq!(400, status);
+    let (status, _) = get(&app, &format!("/leaderboards?quiz_id={}&tag=async", quiz.id)).await;
+    assert_eq!(400, status);
+}
+
+#[tokio::test]
+async fn generated_quizzes_only_rank_on_their_own_board() {
+    let app = spawn_app().await;
+    let quiz = common::quiz("async").tags(&["async"]).questions(2).create(&app).await;
+    play(&app, &quiz, "alice@example.com", 1).await;
+
+    let generated: serde_json::Value = app.api_client
+        .post(format!("{}/quizzes/generate", &app.address))
+        .json(&serde_json::json!({ "count": 2, "tags": ["async"], "seed": 1 }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let generated = common::QuizFixture::from_json(generated);
+    play(&app, &generated, "dave@example.com", 2).await;
+
+    let (_, board) = get(&app, "/leaderboards").await;
+    assert_eq!(users(&board), ["a***@example.com"]);
+    let (_, board) = get(&app, "/leaderboards?tag=async").await;
+    assert_eq!(users(&board), ["a***@example.com"]);
+    let (_, board) = get(&app, &format!("/leaderboards?quiz_id={}", generated.id)).await;
+    assert_eq!(users(&board), ["d***@example.com"]);
+}
+
+#[tokio::test]
+async fn expired_attempts_rank_like_finished_ones() {
+    let app = spawn_app().await;
+    let quiz = common::quiz("Timed").with("time_limit_seconds", serde_json::json!(1)).questions(1).create(&app).await;
+    let (question_id, right, _) = &quiz.questions[0];
+    let attempt: serde_json::Value = app.api_client
+        .post(format!("{}/quizzes/{}/attempts", &app.address, quiz.id))
+        .json(&serde_json::json!({ "user_email": "dave@example.com" }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let attempt_url = format!("{}/quizzes/{}/attempts/{}", &app.address, quiz.id, attempt["id"].as_str().unwrap());
+    app.api_client
+        .post(format!("{}/answers", &attempt_url))
+        .json(&serde_json::json!({ "question_id": question_id, "option_id": right }))
+        .send()
+        .await
+        .unwrap();
+    play(&app, &quiz, "erin@example.com", 0).await;
+    tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+
+    let attempt: serde_json::Value = app.api_client.get(&attempt_url).send().await.unwrap().json().await.unwrap();
+    assert_eq!(attempt["status"], "expired");
+    let (status, board) = get(&app, &format!("/leaderboards?quiz_id={}", quiz.id)).await;
+    assert_eq!(status, 200);
+    assert_eq!(users(&board), ["d***@example.com", "e***@example.com"]);
+}
@@ -0,0 +1,106 @@
+use crate::common::spawn_app;
+
+mod common;
+
+async fn create_quizzes(app: &common::TestApp, count: usize) -> Vec<String> {
+    let mut ids = Vec::new();
+    for i in 0..count {
+        ids.push(common::quiz(&format!("Quiz {}", i)).create(app).await.id);
+    }
+    ids
+}
+
+fn item_ids(page: &serde_json::Value) -> Vec<&str> {
+    page["items"].as_array().unwrap().iter().map(|q| q["id"].as_str().unwrap()).collect()
+}
+
+#[tokio::test]
+async fn list_quizzes_returns_page_envelope_and_links() {
+    let app = spawn_app().await;
+    let ids = create_quizzes(&app, 5).await;
+
+    let response = app.api_client
+        .get(format!("{}/quizzes?tags=&page=2&per_page=2", &app.address))
+        .send()
+        .await
+        .expect("Failed to list quizzes");
+    assert_eq!(200, response.status().as_u16());
+    let link = response.headers()["link"].to_str().unwrap().to_string();
+    assert_eq!(link, "</quizzes?tags=&page=3&per_page=2>; rel=\"next\", </quizzes?tags=&page=1&per_page=2>; rel=\"prev\"");
+
+    let page: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(item_ids(&page), ids[2..4]);
+    assert_eq!(page["total"], 5);
+    assert_eq!(page["page"], 2);
+    assert_eq!(page["per_page"], 2);
+    assert_eq!(page["next"], "/quizzes?tags=&page=3&per_page=2");
+    assert_eq!(page["prev"], "/quizzes?tags=&page=1&per_page=2");
+
+    let response = app.api_client
+        .get(format!("{}/quizzes?page=0", &app.address))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(400, response.status().as_u16());
+
+    let page: serde_json::Value = app.api_client
+        .get(format!("{}/quizzes?per_page=1000", &app.address))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(page["per_page"], 100);
+}
+
+#[tokio::test]
+async fn cursor_pagination_is_stable_under_inserts() {
+    let app = spawn_app().await;
+    let ids = create_quizzes(&app, 4).await;
+
+    let first: serde_json::Value = app.api_client
+        .get(format!("{}/quizzes?per_page=2", &app.address))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(item_ids(&first), ids[0..2]);
+    let cursor = first["next_cursor"].as_str().unwrap();
+
+    // New quizzes land after existing ones and don't shift the pages already handed out
+    let added = create_quizzes(&app, 1).await;
+
+    let second: serde_json::Value = app.api_client
+        .get(format!("{}/quizzes?per_page=2&cursor={}", &app.address, cursor))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(item_ids(&second), ids[2..4]);
+    assert_eq!(second["page"], serde_json::Value::Null);
+    assert_eq!(second["prev"], serde_json::Value::Null);
+
+    let next = second["next"].as_str().unwrap();
+    let third: serde_json::Value = app.api_client
+        .get(format!("{}{}", &app.address, next))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(item_ids(&third), added);
+    assert_eq!(third["next_cursor"], serde_json::Value::Null);
+
+    let response = app.api_client
+        .get(format!("{}/quizzes?sort=difficulty&cursor={}", &app.address, cursor))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(400, response.status().as_u16());
+}
@@ -0,0 +1,125 @@
+use crate::common::spawn_app;
+
+mod common;
+
+async fn send_patch(app: &common::TestApp, quiz_id: &str, content_type: &str, body: serde_json::Value) -> reqwest::Response {
+    app.api_client
+        .patch(format!("{}/quizzes/{}", &app.address, quiz_id))
+        .header("Content-Type", content_type)
+        .body(body.to_string())
+        .send()
+        .await
+        .expect("Failed to patch quiz")
+}
+
+#[tokio::test]
+async fn merge_patch_can_clear_fields() {
+    let app = spawn_app().await;
+    let category: serde_json::Value = app.api_client
+        .post(format!("{}/categories", &app.address))
+        .json(&serde_json::json!({ "name": "Patching" }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let quiz = common::post_quiz(&app, serde_json::json!({
+        "title": "Before",
+        "category_id": category["id"],
+        "time_limit_seconds": 60,
+        "tags": ["old"],
+        "questions": [{ "text": "Q", "options": [{ "text": "A", "is_correct": true }] }]
+    })).await;
+    let quiz_id = quiz["id"].as_str().unwrap();
+
+    let response = send_patch(&app, quiz_id, "application/merge-patch+json", serde_json::json!({
+        "title": "After",
+        "category_id": null,
+        "time_limit_seconds": null,
+        "tags": ["New Tag"]
+    })).await;
+    assert_eq!(200, response.status().as_u16());
+    let patched: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(patched["title"], "After");
+    assert_eq!(patched["category_id"], serde_json::Value::Null);
+    assert_eq!(patched["time_limit_seconds"], serde_json::Value::Null);
+    assert_eq!(patched["tags"], serde_json::json!(["new-tag"]));
+    assert_eq!(patched["questions"], quiz["questions"]);
+
+    // Invalid results are rejected and nothing is stored
+    for body in [serde_json::json!({ "title": "" }), serde_json::json!({ "id": "0E9S17M560002" }), serde_json::json!({ "questions": "none" }), serde_json::json!({ "generated": true })] {
+        let response = send_patch(&app, quiz_id, "application/merge-patch+json", body).await;
+        assert_eq!(422, response.status().as_u16());
+    }
+    let stored: serde_json::Value = app.api_client
+        .get(format!("{}/quizzes/{}", &app.address, quiz_id))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(stored, patched);
+
+    let response = send_patch(&app, quiz_id, "application/json", serde_json::json!({ "title": "X" })).await;
+    assert_eq!(415, response.status().as_u16());
+}
+
+#[tokio::test]
+async fn json_patch_edits_questions_and_answer_key() {
+    let app = spawn_app().await;
+    let quiz = common::post_quiz(&app, serde_json::json!({
+        "title": "Patch Me",
+        "questions": [{
+            "text": "Pick one",
+            "options": [
+                { "text": "Right", "is_correct": true },
+                { "text": "Wrong", "is_correct": false }
+            ]
+        }]
+    })).await;
+    let quiz_id = quiz["id"].as_str().unwrap();
+    let question_id = quiz["questions"][0]["id"].as_str().unwrap();
+    let wrong_id = quiz["questions"][0]["options"][1]["id"].as_str().unwrap();
+
+    let response = send_patch(&app, quiz_id, "application/json-patch+json", serde_json::json!([
+        { "op": "test", "path": "/questions/0/options/1/is_correct", "value": false },
+        { "op": "replace", "path": "/questions/0/options/0/is_correct", "value": false },
+        { "op": "replace", "path": "/questions/0/options/1/is_correct", "value": true },
+        { "op": "add", "path": "/questions/-", "value": {
+            "text": "Added", "options": [{ "text": "Yes", "is_correct": true }]
+        } }
+    ])).await;
+    assert_eq!(200, response.status().as_u16());
+    let patched: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(patched["questions"][0]["id"], question_id);
+    assert!(patched["questions"][1]["id"].is_string());
+    // The answer key is patched but still not exposed
+    assert!(patched["questions"][0]["options"][0].get("is_correct").is_none());
+
+    let answer: serde_json::Value = app.api_client
+        .post(format!("{}/quizzes/{}/solve", &app.address, quiz_id))
+        .json(&serde_json::json!({ "question_id": question_id, "option_id": wrong_id }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(answer["correct"], true);
+
+    let response = send_patch(&app, quiz_id, "application/json-patch+json", serde_json::json!([
+        { "op": "test", "path": "/title", "value": "Someone else's title" },
+        { "op": "replace", "path": "/title", "value": "Lost update" }
+    ])).await;
+    assert_eq!(409, response.status().as_u16());
+
+    let response = send_patch(&app, quiz_id, "application/json-patch+json", serde_json::json!([
+        { "op": "remove", "path": "/questions/5" }
+    ])).await;
+    assert_eq!(422, response.status().as_u16());
+
+    let response = send_patch(&app, quiz_id, "application/json-patch+json", serde_json::json!({ "op": "remove" })).await;
+    assert_eq!(400, response.status().as_u16());
+}
@@ -0,0 +1,119 @@
+use crate::common::spawn_app;
+
+mod common;
+
+/// Questions named after their difficulty: Hard, Medium, Easy.
+async fn create_quiz(app: &common::TestApp) -> common::QuizFixture {
+    let question = |text: &str, difficulty: &str| serde_json::json!({ "text": text, "difficulty": difficulty });
+    common::quiz("Practice Quiz")
+        .tags(&["practice"])
+        .question(question("Hard", "advanced"))
+        .question(question("Medium", "intermediate"))
+        .question(question("Easy", "beginner"))
+        .create(app)
+        .await
+}
+
+async fn start_session(app: &common::TestApp, length: usize) -> String {
+    let response = app.api_client
+        .post(format!("{}/practice/sessions", &app.address))
+        .json(&serde_json::json!({ "user_email": "learner@example.com", "tags": ["practice"], "length": length }))
+        .send()
+        .await
+        .expect("Failed to start session");
+    assert_eq!(201, response.status().as_u16());
+    let session: serde_json::Value = response.json().await.unwrap();
+    session["id"].as_str().unwrap().to_string()
+}
+
+async fn next(app: &common::TestApp, session_id: &str) -> reqwest::Response {
+    app.api_client
+        .get(format!("{}/practice/sessions/{}/next", &app.address, session_id))
+        .send()
+        .await
+        .expect("Failed to fetch next question")
+}
+
+#[tokio::test]
+async fn practice_serves_questions_matched_to_ability() {
+    let app = spawn_app().await;
+    let quiz = create_quiz(&app).await;
+    let session_id = start_session(&app, 2).await;
+
+    // A new learner starts on the question they most likely, but not surely, get right
+    let first: serde_json::Value = next(&app, &session_id).await.json().await.unwrap();
+    assert_eq!(first["question"]["text"], "Easy");
+    assert_eq!(first["number"], 1);
+    assert!(first["expected_success"].as_f64().unwrap() > 0.5);
+
+    // Asking again repeats the unanswered question
+    let again: serde_json::Value = next(&app, &session_id).await.json().await.unwrap();
+    assert_eq!(again["question"]["id"], first["question"]["id"]);
+
+    let (easy_id, easy_right, _) = &quiz.questions[2];
+    let answer: serde_json::Value = app.api_client
+        .post(format!("{}/practice/sessions/{}/answers", &app.address, session_id))
+        .json(&serde_json::json!({ "question_id": easy_id, "option_id": easy_right }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(answer["correct"], true);
+    assert!(answer["ability"].as_f64().unwrap() > 1500.0);
+    assert_eq!(answer["remaining"], 1);
+
+    let second: serde_json::Value = next(&app, &session_id).await.json().await.unwrap();
+    assert_eq!(second["question"]["text"], "Medium");
+    assert_eq!(second["number"], 2);
+    let (medium_id, medium_right, _) = &quiz.questions[1];
+    app.api_client
+        .post(format!("{}/practice/sessions/{}/answers", &app.address, session_id))
+        .json(&serde_json::json!({ "question_id": medium_id, "option_id": medium_right }))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(204, next(&app, &session_id).await.status().as_u16());
+
+    let session: serde_json::Value = app.api_client
+        .get(format!("{}/practice/sessions/{}", &app.address, session_id))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(session["correct_count"], 2);
+    assert_eq!(session["asked"].as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn practice_answers_must_match_the_served_question() {
+    let app = spawn_app().await;
+    let quiz = create_quiz(&app).await;
+    let session_id = start_session(&app, 3).await;
+    let (hard_id, hard_right, _) = &quiz.questions[0];
+
+    // Nothing served yet
+    let response = app.api_client
+        .post(format!("{}/practice/sessions/{}/answers", &app.address, session_id))
+        .json(&serde_json::json!({ "question_id": hard_id, "option_id": hard_right }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(409, response.status().as_u16());
+
+    next(&app, &session_id).await;
+    let response = app.api_client
+        .post(format!("{}/practice/sessions/{}/answers", &app.address, session_id))
+        .json(&serde_json::json!({ "question_id": hard_id, "option_id": hard_right }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(409, response.status().as_u16());
+
+    let response = next(&app, &coding_quiz_api::id::Id::new().to_string()).await;
+    assert_eq!(404, response.status().as_u16());
+}
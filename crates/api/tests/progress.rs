@@ -0,0 +1,111 @@
+use crate::common::spawn_app;
+
+mod common;
+
+async fn solve(app: &common::TestApp, quiz_id: &str, question_id: &str, option_id: &str, user_email: &str) {
+    let response = app.api_client
+        .post(format!("{}/quizzes/{}/solve", &app.address, quiz_id))
+        .json(&serde_json::json!({
+            "question_id": question_id,
+            "option_id": option_id,
+            "user_email": user_email
+        }))
+        .send()
+        .await
+        .expect("Failed to submit");
+    assert_eq!(200, response.status().as_u16());
+}
+
+#[tokio::test]
+async fn progress_reports_accuracy_per_tag_and_category() {
+    let app = spawn_app().await;
+
+    let category: serde_json::Value = app.api_client
+        .post(format!("{}/categories", &app.address))
+        .json(&serde_json::json!({ "name": "JavaScript" }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let category_id = category["id"].as_str().unwrap();
+
+    let common::QuizFixture { id: async_quiz, questions: async_questions, .. } = common::quiz("Async")
+        .tags(&["async", "javascript"])
+        .with("category_id", serde_json::json!(category_id))
+        .questions(3)
+        .create(&app)
+        .await;
+    let common::QuizFixture { id: dom_quiz, questions: dom_questions, .. } = common::quiz("Dom").tags(&["dom"]).questions(2).create(&app).await;
+
+    let user = "Learner@Example.com";
+    solve(&app, &async_quiz, &async_questions[0].0, &async_questions[0].1, user).await;
+    solve(&app, &async_quiz, &async_questions[1].0, &async_questions[1].2, user).await;
+    // Answers inside an attempt count too
+    let attempt: serde_json::Value = app.api_client
+        .post(format!("{}/quizzes/{}/attempts", &app.address, dom_quiz))
+        .json(&serde_json::json!({ "user_email": "learner@example.com" }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    app.api_client
+        .post(format!("{}/quizzes/{}/attempts/{}/answers", &app.address, dom_quiz, attempt["id"].as_str().unwrap()))
+        .json(&serde_json::json!({ "question_id": dom_questions[0].0, "option_id": dom_questions[0].1 }))
+        .send()
+        .await
+        .unwrap();
+    // Another user's answers don't leak in
+    solve(&app, &dom_quiz, &dom_questions[1].0, &dom_questions[1].1, "someone@example.com").await;
+
+    let response = app.api_client
+        .get(format!("{}/me/progress", &app.address))
+        .query(&[("user_email", "learner@example.com")])
+        .send()
+        .await
+        .expect("Failed to get progress");
+    assert_eq!(200, response.status().as_u16());
+    let report: serde_json::Value = response.json().await.unwrap();
+
+    assert_eq!(report["total_answers"], 3);
+    assert_eq!(report["correct_answers"], 2);
+    assert_eq!(report["questions_total"], 5);
+    assert_eq!(report["questions_seen"], 3);
+    assert_eq!(report["questions_never_attempted"], 2);
+
+    let tag = |name: &str| report["by_tag"].as_array().unwrap().iter()
+        .find(|t| t["name"] == name).unwrap().clone();
+    let async_tag = tag("async");
+    assert_eq!(async_tag["answers"], 2);
+    assert_eq!(async_tag["accuracy"], 0.5);
+    assert_eq!(async_tag["questions_never_attempted"], 1);
+    assert_eq!(tag("dom")["accuracy"], 1.0);
+
+    let categories = report["by_category"].as_array().unwrap();
+    assert_eq!(categories.len(), 1);
+    assert_eq!(categories[0]["id"], category_id);
+    assert_eq!(categories[0]["name"], "JavaScript");
+    assert_eq!(categories[0]["questions_seen"], 2);
+}
+
+#[tokio::test]
+async fn progress_for_unknown_user_is_empty() {
+    let app = spawn_app().await;
+    common::quiz("Dom").tags(&["dom"]).questions(2).create(&app).await;
+
+    let report: serde_json::Value = app.api_client
+        .get(format!("{}/me/progress", &app.address))
+        .query(&[("user_email", "nobody@example.com")])
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(report["total_answers"], 0);
+    assert_eq!(report["accuracy"], 0.0);
+    assert_eq!(report["questions_never_attempted"], 2);
+}
@@ -0,0 +1,186 @@
+use crate::common::spawn_app;
+
+mod common;
+
+async fn get_quiz(app: &common::TestApp, quiz_id: &str) -> serde_json::Value {
+    app.api_client
+        .get(format!("{}/quizzes/{}", &app.address, quiz_id))
+        .send()
+        .await
+        .expect("Failed to get quiz")
+        .json()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn questions_and_options_are_edited_in_place() {
+    let app = spawn_app().await;
+    let quiz: serde_json::Value = app.api_client
+        .post(format!("{}/quizzes", &app.address))
+        .json(&serde_json::json!({
+            "title": "Editing",
+            "questions": [{
+                "text": "What is 2 + 2?",
+                "options": [
+                    { "text": "4", "is_correct": true },
+                    { "text": "5", "is_correct": false }
+                ]
+            }]
+        }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let quiz_id = quiz["id"].as_str().unwrap();
+    let first = quiz["questions"][0]["id"].as_str().unwrap();
+    let right = quiz["questions"][0]["options"][0]["id"].as_str().unwrap();
+    let typo = quiz["questions"][0]["options"][1]["id"].as_str().unwrap();
+
+    // Fixing one option leaves every other ID alone
+    let response = app.api_client
+        .put(format!("{}/quizzes/{}/questions/{}/options/{}", &app.address, quiz_id, first, typo))
+        .json(&serde_json::json!({ "text": "22" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(200, response.status().as_u16());
+    let edited = get_quiz(&app, quiz_id).await;
+    assert_eq!(edited["questions"][0]["id"], first);
+    assert_eq!(edited["questions"][0]["options"][0]["id"], right);
+    assert_eq!(edited["questions"][0]["options"][1]["id"], typo);
+    assert_eq!(edited["questions"][0]["options"][1]["text"], "22");
+
+    let response = app.api_client
+        .post(format!("{}/quizzes/{}/questions", &app.address, quiz_id))
+        .json(&serde_json::json!({
+            "text": "What is 3 * 3?",
+            "options": [{ "text": "9", "is_correct": true }]
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(201, response.status().as_u16());
+    let added: serde_json::Value = response.json().await.unwrap();
+    let second = added["id"].as_str().unwrap();
+
+    let response = app.api_client
+        .put(format!("{}/quizzes/{}/questions/{}", &app.address, quiz_id, second))
+        .json(&serde_json::json!({ "text": "What is 3 x 3?", "explanation": "Nine." }))
+        .send()
+        .await
+        .unwrap();
+    let updated: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(updated["text"], "What is 3 x 3?");
+    assert_eq!(updated["options"][0]["id"], added["options"][0]["id"]);
+
+    let response = app.api_client
+        .put(format!("{}/quizzes/{}/questions/order", &app.address, quiz_id))
+        .json(&serde_json::json!({ "ids": [second, first] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(200, response.status().as_u16());
+    let reordered: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(reordered["questions"][0]["id"], second);
+
+    let response = app.api_client
+        .put(format!("{}/quizzes/{}/questions/order", &app.address, quiz_id))
+        .json(&serde_json::json!({ "ids": [second, second] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(422, response.status().as_u16());
+
+    let response = app.api_client
+        .put(format!("{}/quizzes/{}/questions/order", &app.address, quiz_id))
+        .json(&serde_json::json!({ "ids": [second] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(400, response.status().as_u16());
+
+    let response = app.api_client
+        .put(format!("{}/quizzes/{}/questions/{}/options/order", &app.address, quiz_id, first))
+        .json(&serde_json::json!({ "ids": [typo, right] }))
+        .send()
+        .await
+        .unwrap();
+    let question: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(question["options"][0]["id"], typo);
+
+    let response = app.api_client
+        .post(format!("{}/quizzes/{}/questions/{}/options", &app.address, quiz_id, first))
+        .json(&serde_json::json!({ "text": "3", "is_correct": false }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(201, response.status().as_u16());
+    let option: serde_json::Value = response.json().await.unwrap();
+    let response = app.api_client
+        .delete(format!("{}/quizzes/{}/questions/{}/options/{}", &app.address, quiz_id, first, option["id"].as_str().unwrap()))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(204, response.status().as_u16());
+
+    // Edited content is answerable straight away
+    let response = app.api_client
+        .post(format!("{}/quizzes/{}/solve", &app.address, quiz_id))
+        .json(&serde_json::json!({ "question_id": first, "option_id": right }))
+        .send()
+        .await
+        .unwrap();
+    let answer: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(answer["correct"], true);
+
+    let response = app.api_client
+        .delete(format!("{}/quizzes/{}/questions/{}", &app.address, quiz_id, second))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(204, response.status().as_u16());
+    let remaining = get_quiz(&app, quiz_id).await;
+    assert_eq!(remaining["questions"].as_array().unwrap().len(), 1);
+
+    let response = app.api_client
+        .delete(format!("{}/quizzes/{}/questions/{}", &app.address, quiz_id, second))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(404, response.status().as_u16());
+}
+
+#[tokio::test]
+async fn generated_quizzes_cannot_be_edited() {
+    let app = spawn_app().await;
+    app.api_client
+        .post(format!("{}/quizzes", &app.address))
+        .json(&serde_json::json!({
+            "title": "Source",
+            "tags": ["pool"],
+            "questions": [{ "text": "Q", "options": [{ "text": "A", "is_correct": true }] }]
+        }))
+        .send()
+        .await
+        .unwrap();
+    let generated: serde_json::Value = app.api_client
+        .post(format!("{}/quizzes/generate", &app.address))
+        .json(&serde_json::json!({ "count": 1, "tags": ["pool"] }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+
+    let response = app.api_client
+        .put(format!("{}/quizzes/{}/questions/{}", &app.address, generated["id"].as_str().unwrap(), generated["questions"][0]["id"].as_str().unwrap()))
+        .json(&serde_json::json!({ "text": "Changed" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(403, response.status().as_u16());
+}
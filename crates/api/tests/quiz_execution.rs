@@ -3,43 +3,12 @@ use crate::common::spawn_app;
 mod common;
 
 async fn get_quiz_data(app: &common::TestApp) -> (String, String, String, String) {
-    // 1. Create Quiz
-    let create_body = serde_json::json!({
-        "title": "Exec Quiz",
-        "category_id": null,
-        "questions": [
-             {
-                "text": "Exec Q1",
-                "explanation": "Exec Explanation",
-                "options": [
-                    { "text": "Correct Opt", "is_correct": true },
-                    { "text": "Wrong Opt", "is_correct": false }
-                ]
-            }
-        ],
-        "tags": []
-    });
-
-    let q_res = app.api_client
-        .post(&format!("{}/quizzes", &app.address))
-        .json(&create_body)
-        .send()
-        .await
-        .expect("Failed to create quiz");
-    let q_json: serde_json::Value = q_res.json().await.unwrap();
-    let quiz_id = q_json["id"].as_str().unwrap().to_string();
-    
-    // Get question and option IDs from response
-    let questions = q_json["questions"].as_array().unwrap();
-    let question_obj = &questions[0];
-    let question_id = question_obj["id"].as_str().unwrap().to_string();
-    let options = question_obj["options"].as_array().unwrap();
-    
-    // Find IDs by text
-    let correct_id = options.iter().find(|o| o["text"] == "Correct Opt").unwrap()["id"].as_str().unwrap().to_string();
-    let wrong_id = options.iter().find(|o| o["text"] == "Wrong Opt").unwrap()["id"].as_str().unwrap().to_string();
-    
-    (quiz_id, question_id, correct_id, wrong_id)
+    let quiz = common::quiz("Exec Quiz")
+        .question(serde_json::json!({ "text": "Exec Q1", "explanation": "Exec Explanation" }))
+        .create(app)
+        .await;
+    let (question_id, correct_id, wrong_id) = quiz.questions[0].clone();
+    (quiz.id, question_id, correct_id, wrong_id)
 }
 
 #[tokio::test]
@@ -53,7 +22,7 @@ async fn submit_correct_answer_returns_true() {
     });
 
     let response = app.api_client
-        .post(&format!("{}/quizzes/{}/solve", &app.address, quiz_id))
+        .post(format!("{}/quizzes/{}/solve", &app.address, quiz_id))
         .json(&submit_body)
         .send()
         .await
@@ -76,7 +45,7 @@ async fn submit_incorrect_answer_returns_false() {
     });
 
     let response = app.api_client
-        .post(&format!("{}/quizzes/{}/solve", &app.address, quiz_id))
+        .post(format!("{}/quizzes/{}/solve", &app.address, quiz_id))
         .json(&submit_body)
         .send()
         .await
@@ -89,3 +58,125 @@ async fn submit_incorrect_answer_returns_false() {
     assert_eq!(res_json["explanation"], "Exec Explanation");
 }
 
+
+async fn create_quiz_with_policy(app: &common::TestApp, policy: &str) -> (String, String, String) {
+    let create_body = serde_json::json!({
+        "title": "Feedback Quiz",
+        "reveal_policy": policy,
+        "questions": [{
+            "text": "Which method adds to the end of an array?",
+            "explanation": "push appends elements.",
+            "options": [
+                { "text": "push", "is_correct": true, "description": "push appends to the end." },
+                { "text": "shift", "is_correct": false, "description": "shift removes from the start." }
+            ]
+        }],
+        "tags": []
+    });
+
+    let quiz = common::post_quiz(app, create_body).await;
+    let question = &quiz["questions"][0];
+    assert!(question["options"][0].get("description").is_none());
+    let wrong_id = question["options"].as_array().unwrap().iter()
+        .find(|o| o["text"] == "shift").unwrap()["id"].as_str().unwrap().to_string();
+
+    (quiz["id"].as_str().unwrap().to_string(), question["id"].as_str().unwrap().to_string(), wrong_id)
+}
+
+#[tokio::test]
+async fn wrong_answer_returns_feedback_according_to_reveal_policy() {
+    let app = spawn_app().await;
+
+    for (policy, expect_feedback, expect_correct) in [
+        ("always", true, true),
+        ("after_final_attempt", true, false),
+        ("never", false, false),
+    ] {
+        let (quiz_id, question_id, wrong_id) = create_quiz_with_policy(&app, policy).await;
+
+        let res_json: serde_json::Value = app.api_client
+            .post(format!("{}/quizzes/{}/solve", &app.address, quiz_id))
+            .json(&serde_json::json!({ "question_id": question_id, "option_id": wrong_id }))
+            .send()
+            .await
+            .expect("Failed to submit")
+            .json()
+            .await
+            .unwrap();
+
+        assert_eq!(res_json["correct"], false);
+        if expect_feedback {
+            assert_eq!(res_json["option_feedback"], "shift removes from the start.", "policy {}", policy);
+        } else {
+            assert!(res_json.get("option_feedback").is_none(), "policy {}", policy);
+        }
+        if expect_correct {
+            assert_eq!(res_json["correct_options"][0]["text"], "push", "policy {}", policy);
+        } else {
+            assert!(res_json.get("correct_options").is_none(), "policy {}", policy);
+        }
+    }
+}
+
+#[tokio::test]
+async fn submit_answer_is_scoped_to_the_quiz_in_the_path() {
+    let app = spawn_app().await;
+    let (quiz_id, question_id, correct_id, _) = get_quiz_data(&app).await;
+    let (other_quiz_id, other_question_id, other_correct_id, _) = get_quiz_data(&app).await;
+    let unknown = coding_quiz_api::id::Id::new().to_string();
+
+    let cases = [
+        // Unknown quiz
+        (unknown.clone(), question_id.clone(), correct_id.clone(), "Quiz not found"),
+        // Question belongs to a different quiz
+        (other_quiz_id.clone(), question_id.clone(), correct_id.clone(), "Question not found in quiz"),
+        // Unknown question
+        (quiz_id.clone(), unknown.clone(), correct_id.clone(), "Question not found in quiz"),
+        // Unknown option
+        (quiz_id.clone(), question_id.clone(), unknown.clone(), "Option not found"),
+    ];
+
+    for (path_quiz, question, option, error) in cases {
+        let response = app.api_client
+            .post(format!("{}/quizzes/{}/solve", &app.address, path_quiz))
+            .json(&serde_json::json!({ "question_id": question, "option_id": option }))
+            .send()
+            .await
+            .expect("Failed to submit");
+
+        assert_eq!(404, response.status().as_u16());
+        let res_json: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(res_json["error"], error);
+    }
+
+    // Deleting the quiz removes its questions from the index
+    app.api_client
+        .delete(format!("{}/quizzes/{}", &app.address, quiz_id))
+        .send()
+        .await
+        .unwrap();
+    let response = app.api_client
+        .post(format!("{}/quizzes/{}/solve", &app.address, quiz_id))
+        .json(&serde_json::json!({ "question_id": question_id, "option_id": correct_id }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(404, response.status().as_u16());
+
+    // The remaining quiz moved up and is still found, also after an edit to its text
+    app.api_client
+        .put(format!("{}/quizzes/{}/questions/{}", &app.address, other_quiz_id, other_question_id))
+        .json(&serde_json::json!({ "text": "Renamed" }))
+        .send()
+        .await
+        .unwrap();
+    let response = app.api_client
+        .post(format!("{}/quizzes/{}/solve", &app.address, other_quiz_id))
+        .json(&serde_json::json!({ "question_id": other_question_id, "option_id": other_correct_id }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(200, response.status().as_u16());
+    let json: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(json["correct"], true);
+}
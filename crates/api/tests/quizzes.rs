@@ -27,7 +27,7 @@ async fn quiz_crud_works() {
     });
 
     let response = app.api_client
-        .post(&format!("{}/quizzes", &app.address))
+        .post(format!("{}/quizzes", &app.address))
         .json(&create_body)
         .send()
         .await
@@ -40,7 +40,7 @@ async fn quiz_crud_works() {
     
     // 2. Get Quiz
     let response = app.api_client
-        .get(&format!("{}/quizzes/{}", &app.address, quiz_id))
+        .get(format!("{}/quizzes/{}", &app.address, quiz_id))
         .send()
         .await
         .expect("Failed to get quiz");
@@ -58,7 +58,7 @@ async fn quiz_crud_works() {
     });
     
     let response = app.api_client
-        .put(&format!("{}/quizzes/{}", &app.address, quiz_id))
+        .put(format!("{}/quizzes/{}", &app.address, quiz_id))
         .json(&update_body)
         .send()
         .await
@@ -70,7 +70,7 @@ async fn quiz_crud_works() {
     
     // 4. Delete Quiz
     let response = app.api_client
-        .delete(&format!("{}/quizzes/{}", &app.address, quiz_id))
+        .delete(format!("{}/quizzes/{}", &app.address, quiz_id))
         .send()
         .await
         .expect("Failed to delete quiz");
@@ -79,7 +79,7 @@ async fn quiz_crud_works() {
 
     // 5. Verify Deletion
     let response = app.api_client
-        .get(&format!("{}/quizzes/{}", &app.address, quiz_id))
+        .get(format!("{}/quizzes/{}", &app.address, quiz_id))
         .send()
         .await
         .expect("Failed to get quiz");
@@ -101,7 +101,7 @@ async fn list_quizzes_filtering_works() {
                 "questions": [],
                 "tags": []
             });
-            let res = client.post(&format!("{}/quizzes", addr))
+            let res = client.post(format!("{}/quizzes", addr))
                 .json(&body)
                 .send()
                 .await
@@ -113,13 +113,13 @@ async fn list_quizzes_filtering_works() {
 
     // List all
     let response = app.api_client
-        .get(&format!("{}/quizzes", &app.address))
+        .get(format!("{}/quizzes", &app.address))
         .query(&[("per_page", "100")])
         .send()
         .await
         .expect("Failed to list quizzes");
     let json: serde_json::Value = response.json().await.unwrap();
-    let all_quizzes = json.as_array().unwrap();
+    let all_quizzes = json["items"].as_array().unwrap();
     
     for id in &ids {
         assert!(all_quizzes.iter().any(|q| q["id"].as_str().unwrap() == id));
@@ -128,18 +128,18 @@ async fn list_quizzes_filtering_works() {
     // List excluding the first one
     let excluded_id = &ids[0];
     let response = app.api_client
-        .get(&format!("{}/quizzes", &app.address))
+        .get(format!("{}/quizzes", &app.address))
         .query(&[("exclude_ids", excluded_id.as_str()), ("per_page", "100")])
         .send()
         .await
         .expect("Failed to list quizzes");
     
     let json: serde_json::Value = response.json().await.unwrap();
-    let filtered_quizzes = json.as_array().unwrap();
+    let filtered_quizzes = json["items"].as_array().unwrap();
     
     assert!(!filtered_quizzes.iter().any(|q| q["id"].as_str().unwrap() == excluded_id));
-    assert!(filtered_quizzes.iter().any(|q| q["id"].as_str().unwrap() == &ids[1]));
-    assert!(filtered_quizzes.iter().any(|q| q["id"].as_str().unwrap() == &ids[2]));
+    assert!(filtered_quizzes.iter().any(|q| q["id"].as_str().unwrap() == ids[1]));
+    assert!(filtered_quizzes.iter().any(|q| q["id"].as_str().unwrap() == ids[2]));
 }
 
 #[tokio::test]
@@ -148,7 +148,7 @@ async fn get_non_existent_quiz_fails() {
     let non_existent_id = Id::new();
 
     let response = app.api_client
-        .get(&format!("{}/quizzes/{}", &app.address, non_existent_id))
+        .get(format!("{}/quizzes/{}", &app.address, non_existent_id))
         .send()
         .await
         .expect("Failed to execute request.");
@@ -162,7 +162,7 @@ async fn delete_non_existent_quiz_fails() {
     let non_existent_id = Id::new();
 
     let response = app.api_client
-        .delete(&format!("{}/quizzes/{}", &app.address, non_existent_id))
+        .delete(format!("{}/quizzes/{}", &app.address, non_existent_id))
         .send()
         .await
         .expect("Failed to execute request.");
@@ -177,7 +177,7 @@ async fn update_non_existent_quiz_fails() {
     let update_body = serde_json::json!({ "title": "New Title" });
 
     let response = app.api_client
-        .put(&format!("{}/quizzes/{}", &app.address, non_existent_id))
+        .put(format!("{}/quizzes/{}", &app.address, non_existent_id))
         .json(&update_body)
         .send()
         .await
@@ -195,13 +195,214 @@ async fn create_quiz_invalid_data_fails() {
     });
 
     let response = app.api_client
-        .post(&format!("{}/quizzes", &app.address))
+        .post(format!("{}/quizzes", &app.address))
         .json(&invalid_body)
         .send()
         .await
         .expect("Failed to execute request.");
     
-    // Should be 400 Bad Request due to Json deserialization error
-    assert_eq!(400, response.status().as_u16());
+    // Deserialization errors are field errors like any other
+    assert_eq!(422, response.status().as_u16());
 }
 
+
+#[tokio::test]
+async fn list_quizzes_filters_and_sorts_by_difficulty_and_time() {
+    let app = spawn_app().await;
+
+    let quizzes = [
+        ("Hard Quiz", "advanced", 120),
+        ("Easy Quiz", "beginner", 30),
+        ("Medium Quiz", "intermediate", 60),
+    ];
+    for (title, difficulty, seconds) in quizzes {
+        let body = serde_json::json!({
+            "title": title,
+            "difficulty": difficulty,
+            "questions": [{
+                "text": "Q",
+                "estimated_time_seconds": seconds,
+                "options": [{ "text": "A", "is_correct": true }]
+            }],
+            "tags": []
+        });
+        let response = app.api_client
+            .post(format!("{}/quizzes", &app.address))
+            .json(&body)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(201, response.status().as_u16());
+    }
+
+    // Filter by difficulty
+    let response = app.api_client
+        .get(format!("{}/quizzes", &app.address))
+        .query(&[("difficulty", "beginner")])
+        .send()
+        .await
+        .expect("Failed to list quizzes");
+    assert_eq!(200, response.status().as_u16());
+    let json: serde_json::Value = response.json().await.unwrap();
+    let titles: Vec<&str> = json["items"].as_array().unwrap().iter().map(|q| q["title"].as_str().unwrap()).collect();
+    assert_eq!(titles, vec!["Easy Quiz"]);
+
+    // Filter by time and sort by difficulty descending
+    let response = app.api_client
+        .get(format!("{}/quizzes", &app.address))
+        .query(&[("max_estimated_time", "60"), ("sort", "difficulty"), ("order", "desc")])
+        .send()
+        .await
+        .expect("Failed to list quizzes");
+    let json: serde_json::Value = response.json().await.unwrap();
+    let titles: Vec<&str> = json["items"].as_array().unwrap().iter().map(|q| q["title"].as_str().unwrap()).collect();
+    assert_eq!(titles, vec!["Medium Quiz", "Easy Quiz"]);
+
+    // Sort by estimated time
+    let response = app.api_client
+        .get(format!("{}/quizzes", &app.address))
+        .query(&[("sort", "estimated_time")])
+        .send()
+        .await
+        .expect("Failed to list quizzes");
+    let json: serde_json::Value = response.json().await.unwrap();
+    let titles: Vec<&str> = json["items"].as_array().unwrap().iter().map(|q| q["title"].as_str().unwrap()).collect();
+    assert_eq!(titles, vec!["Easy Quiz", "Medium Quiz", "Hard Quiz"]);
+
+    // Random quiz honours the difficulty filter
+    let response = app.api_client
+        .get(format!("{}/quizzes/random", &app.address))
+        .query(&[("difficulty", "advanced")])
+        .send()
+        .await
+        .expect("Failed to get random quiz");
+    assert_eq!(200, response.status().as_u16());
+    let json: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(json["title"], "Hard Quiz");
+    assert_eq!(json["questions"][0]["estimated_time_seconds"], 120);
+}
+
+#[tokio::test]
+async fn random_quiz_prefers_unseen_content_and_honours_seed_and_exclusions() {
+    let app = spawn_app().await;
+
+    let mut quizzes = Vec::new();
+    for i in 0..4 {
+        let body = serde_json::json!({
+            "title": format!("Random Quiz {}", i),
+            "questions": [{
+                "text": "Q",
+                "options": [{ "text": "A", "is_correct": true }]
+            }],
+            "tags": ["pick"]
+        });
+        let quiz: serde_json::Value = app.api_client
+            .post(format!("{}/quizzes", &app.address))
+            .json(&body)
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        quizzes.push(quiz);
+    }
+    let id = |quiz: &serde_json::Value| quiz["id"].as_str().unwrap().to_string();
+
+    let random = |query: Vec<(&'static str, String)>| {
+        let client = app.api_client.clone();
+        let url = format!("{}/quizzes/random", &app.address);
+        async move {
+            let response = client.get(url).query(&query).send().await.unwrap();
+            assert_eq!(200, response.status().as_u16());
+            let json: serde_json::Value = response.json().await.unwrap();
+            json["id"].as_str().unwrap().to_string()
+        }
+    };
+
+    // Same seed, same pick
+    let first = random(vec![("tag", "pick".into()), ("seed", "42".into())]).await;
+    for _ in 0..3 {
+        assert_eq!(first, random(vec![("tag", "pick".into()), ("seed", "42".into())]).await);
+    }
+
+    // Master the first three quizzes: only the fourth is left to recommend
+    for quiz in &quizzes[..3] {
+        let question = &quiz["questions"][0];
+        app.api_client
+            .post(format!("{}/quizzes/{}/solve", &app.address, id(quiz)))
+            .json(&serde_json::json!({
+                "question_id": question["id"],
+                "option_id": question["options"][0]["id"],
+                "user_email": "learner@example.com"
+            }))
+            .send()
+            .await
+            .unwrap();
+    }
+    for seed in 0..10 {
+        let picked = random(vec![
+            ("tag", "pick".into()),
+            ("user_email", "learner@example.com".into()),
+            ("seed", seed.to_string()),
+        ]).await;
+        assert_eq!(picked, id(&quizzes[3]));
+    }
+
+    // Excluding the only fresh quiz falls back to the rest
+    let picked = random(vec![
+        ("tag", "pick".into()),
+        ("user_email", "learner@example.com".into()),
+        ("exclude_ids", id(&quizzes[3])),
+    ]).await;
+    assert_ne!(picked, id(&quizzes[3]));
+}
+
+#[tokio::test]
+async fn list_quizzes_sorts_and_returns_summaries() {
+    let app = spawn_app().await;
+
+    for (title, questions) in [("beta", 3), ("Alpha", 1), ("Gamma", 2)] {
+        let questions: Vec<serde_json::Value> = (0..questions)
+            .map(|i| serde_json::json!({ "text": format!("Q{}", i), "options": [{ "text": "A", "is_correct": true }] }))
+            .collect();
+        app.api_client
+            .post(format!("{}/quizzes", &app.address))
+            .json(&serde_json::json!({ "title": title, "questions": questions, "tags": ["sorting"] }))
+            .send()
+            .await
+            .unwrap();
+    }
+
+    let list = |query: &'static str| {
+        let request = app.api_client.get(format!("{}/quizzes?{}", &app.address, query));
+        async move { request.send().await.unwrap().json::<serde_json::Value>().await.unwrap() }
+    };
+    let titles = |page: &serde_json::Value| -> Vec<String> {
+        page["items"].as_array().unwrap().iter().map(|q| q["title"].as_str().unwrap().to_string()).collect()
+    };
+
+    assert_eq!(titles(&list("sort=title").await), ["Alpha", "beta", "Gamma"]);
+    assert_eq!(titles(&list("sort=question_count&order=desc").await), ["beta", "Gamma", "Alpha"]);
+    assert_eq!(titles(&list("sort=created&order=desc").await), ["Gamma", "Alpha", "beta"]);
+
+    let summary = list("view=summary&sort=title").await;
+    assert_eq!(summary["items"][0]["question_count"], 1);
+    assert_eq!(summary["items"][0]["tags"], serde_json::json!(["sorting"]));
+    assert!(summary["items"][0].get("questions").is_none());
+
+    let picked = list("fields=id,title,question_count&sort=title").await;
+    let first = picked["items"][0].as_object().unwrap();
+    let mut keys: Vec<&str> = first.keys().map(String::as_str).collect();
+    keys.sort();
+    assert_eq!(keys, ["id", "question_count", "title"]);
+
+    for query in ["fields=id,answers", "fields=id&view=summary"] {
+        let response = app.api_client
+            .get(format!("{}/quizzes?{}", &app.address, query))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(400, response.status().as_u16());
+    }
+}
@@ -0,0 +1,95 @@
+use crate::common::spawn_app;
+
+mod common;
+
+async fn solve(app: &common::TestApp, quiz_id: &str, question_id: &str, option_id: &str, user_email: &str) {
+    let response = app.api_client
+        .post(format!("{}/quizzes/{}/solve", &app.address, quiz_id))
+        .json(&serde_json::json!({
+            "question_id": question_id,
+            "option_id": option_id,
+            "user_email": user_email
+        }))
+        .send()
+        .await
+        .expect("Failed to submit");
+    assert_eq!(200, response.status().as_u16());
+}
+
+async fn review_queue(app: &common::TestApp, user_email: &str) -> serde_json::Value {
+    app.api_client
+        .get(format!("{}/me/review?user_email={}", &app.address, user_email))
+        .send()
+        .await
+        .expect("Failed to fetch review queue")
+        .json()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn solve_results_schedule_reviews() {
+    let app = spawn_app().await;
+    let common::QuizFixture { id: quiz_id, questions, .. } = common::quiz("Review Quiz").questions(2).create(&app).await;
+    let user = "reviewer@example.com";
+
+    solve(&app, &quiz_id, &questions[0].0, &questions[0].1, user).await;
+    solve(&app, &quiz_id, &questions[1].0, &questions[1].2, user).await;
+
+    // The missed question is due again right away, the correct one tomorrow
+    let queue = review_queue(&app, user).await;
+    assert_eq!(queue["due_count"], 1);
+    let item = &queue["items"][0];
+    assert_eq!(item["question"]["id"], questions[1].0.as_str());
+    assert_eq!(item["card"]["quiz_id"], quiz_id.as_str());
+    assert_eq!(item["card"]["repetitions"], 0);
+    assert!(queue["next_due_at"].is_string());
+
+    let other = review_queue(&app, "someone-else@example.com").await;
+    assert_eq!(other["due_count"], 0);
+}
+
+#[tokio::test]
+async fn grading_a_review_reschedules_the_question() {
+    let app = spawn_app().await;
+    let common::QuizFixture { id: quiz_id, questions, .. } = common::quiz("Review Quiz").questions(2).create(&app).await;
+    let user = "reviewer@example.com";
+    solve(&app, &quiz_id, &questions[0].0, &questions[0].2, user).await;
+
+    let card: serde_json::Value = app.api_client
+        .post(format!("{}/me/review", &app.address))
+        .json(&serde_json::json!({ "user_email": user, "question_id": questions[0].0, "grade": 5 }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(card["repetitions"], 1);
+    assert_eq!(card["interval_days"], 1);
+
+    let queue = review_queue(&app, user).await;
+    assert_eq!(queue["due_count"], 0);
+}
+
+#[tokio::test]
+async fn grading_rejects_invalid_grades_and_unknown_questions() {
+    let app = spawn_app().await;
+    let questions = common::quiz("Review Quiz").questions(2).create(&app).await.questions;
+
+    let response = app.api_client
+        .post(format!("{}/me/review", &app.address))
+        .json(&serde_json::json!({ "user_email": "a@example.com", "question_id": questions[0].0, "grade": 6 }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(422, response.status().as_u16());
+
+    let response = app.api_client
+        .post(format!("{}/me/review", &app.address))
+        .json(&serde_json::json!({ "user_email": "a@example.com", "question_id": coding_quiz_api::id::Id::new().to_string(), "grade": 3 }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(404, response.status().as_u16());
+}
@@ -0,0 +1,88 @@
+use crate::common::spawn_app;
+
+mod common;
+
+async fn search(app: &common::TestApp, q: &str) -> (u16, serde_json::Value) {
+    let response = app.api_client
+        .get(format!("{}/search", &app.address))
+        .query(&[("q", q)])
+        .send()
+        .await
+        .expect("Failed to search");
+    let status = response.status().as_u16();
+    (status, response.json().await.unwrap())
+}
+
+#[tokio::test]
+async fn search_ranks_and_highlights_matches() {
+    let app = spawn_app().await;
+    common::post_quiz(&app, serde_json::json!({
+        "title": "Array Methods",
+        "questions": [{
+            "text": "What does Array.prototype.map return?",
+            "explanation": "map creates a new array from the callback results.",
+            "options": [
+                { "text": "A new array", "is_correct": true },
+                { "text": "undefined", "is_correct": false }
+            ]
+        }]
+    })).await;
+    common::post_quiz(&app, serde_json::json!({
+        "title": "Promises",
+        "questions": [{
+            "text": "Which state does a settled promise have?",
+            "options": [
+                { "text": "fulfilled", "is_correct": true },
+                { "text": "pending", "is_correct": false }
+            ]
+        }]
+    })).await;
+
+    let (status, results) = search(&app, "Array.prototype.map").await;
+    assert_eq!(200, status);
+    let top = &results["results"][0];
+    assert_eq!(top["kind"], "question");
+    assert_eq!(top["quiz_title"], "Array Methods");
+    assert_eq!(top["highlights"][0]["field"], "question");
+    assert_eq!(top["highlights"][0]["snippet"], "What does <mark>Array.prototype.map</mark> return?");
+
+    // Stemming matches plural and singular
+    let (_, results) = search(&app, "promises").await;
+    assert_eq!(results["total"], 2);
+    assert_eq!(results["results"][0]["kind"], "quiz");
+
+    let (status, _) = search(&app, "  ?! ").await;
+    assert_eq!(400, status);
+}
+
+#[tokio::test]
+async fn search_index_follows_quiz_changes() {
+    let app = spawn_app().await;
+    let quiz = common::post_quiz(&app, serde_json::json!({
+        "title": "Closures",
+        "questions": [{ "text": "Q", "options": [{ "text": "A", "is_correct": true }] }]
+    })).await;
+    let quiz_id = quiz["id"].as_str().unwrap();
+
+    let (_, results) = search(&app, "closure").await;
+    assert_eq!(results["total"], 1);
+
+    app.api_client
+        .put(format!("{}/quizzes/{}", &app.address, quiz_id))
+        .json(&serde_json::json!({ "title": "Scope" }))
+        .send()
+        .await
+        .unwrap();
+    let (_, results) = search(&app, "closure").await;
+    assert_eq!(results["total"], 0);
+    let (_, results) = search(&app, "scope").await;
+    assert_eq!(results["total"], 1);
+
+    app.api_client
+        .delete(format!("{}/quizzes/{}", &app.address, quiz_id))
+        .send()
+        .await
+        .unwrap();
+    let (_, results) = search(&app, "scope").await;
+    assert_eq!(results["total"], 0);
+}
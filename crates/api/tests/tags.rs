@@ -0,0 +1,137 @@
+use crate::common::spawn_app;
+
+mod common;
+
+async fn list_titles(app: &common::TestApp, query: &str) -> Vec<String> {
+    let quizzes: serde_json::Value = app.api_client
+        .get(format!("{}/quizzes?per_page=50&{}", &app.address, query))
+        .send()
+        .await
+        .expect("Failed to list quizzes")
+        .json()
+        .await
+        .unwrap();
+    let mut titles: Vec<String> = quizzes["items"].as_array().unwrap()
+        .iter()
+        .map(|q| q["title"].as_str().unwrap().to_string())
+        .collect();
+    titles.sort();
+    titles
+}
+
+#[tokio::test]
+async fn list_quizzes_filters_by_tags() {
+    let app = spawn_app().await;
+    common::quiz("Async").tags(&["javascript", "async"]).questions(1).create(&app).await;
+    common::quiz("Dom").tags(&["javascript", "dom"]).questions(1).create(&app).await;
+    common::quiz("Ownership").tags(&["rust"]).questions(1).create(&app).await;
+
+    assert_eq!(list_titles(&app, "tags=async,rust").await, ["Async", "Ownership"]);
+    assert_eq!(list_titles(&app, "tags=javascript,dom&tag_mode=all").await, ["Dom"]);
+    assert_eq!(list_titles(&app, "tags=javascript&exclude_tags=dom").await, ["Async"]);
+    assert_eq!(list_titles(&app, "exclude_tags=javascript").await, ["Ownership"]);
+    assert!(list_titles(&app, "tags=python").await.is_empty());
+
+    let response = app.api_client
+        .get(format!("{}/quizzes?tags=rust&tag_mode=some", &app.address))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(422, response.status().as_u16());
+}
+
+#[tokio::test]
+async fn list_tags_reports_usage_counts() {
+    let app = spawn_app().await;
+    common::quiz("Async").tags(&["javascript", "async"]).questions(3).create(&app).await;
+    common::quiz("Dom").tags(&["javascript", "dom"]).questions(2).create(&app).await;
+
+    let tags: serde_json::Value = app.api_client
+        .get(format!("{}/tags", &app.address))
+        .send()
+        .await
+        .expect("Failed to list tags")
+        .json()
+        .await
+        .unwrap();
+
+    let tags = tags.as_array().unwrap();
+    let counts: Vec<(&str, u64, u64)> = tags.iter()
+        .map(|t| (t["name"].as_str().unwrap(), t["quiz_count"].as_u64().unwrap(), t["question_count"].as_u64().unwrap()))
+        .collect();
+    assert_eq!(counts, [("javascript", 2, 5), ("async", 1, 3), ("dom", 1, 2)]);
+    assert!(tags.iter().all(|t| t["id"].is_string()));
+}
+
+#[tokio::test]
+async fn tags_are_normalized_on_write() {
+    let app = spawn_app().await;
+    let quiz = common::quiz("Modules")
+        .tags(&[" JavaScript ", "ES2015", "es6", "Event Loop", "js", "  "])
+        .create(&app)
+        .await
+        .json;
+    assert_eq!(quiz["tags"], serde_json::json!(["javascript", "es6", "event-loop"]));
+
+    let tags: serde_json::Value = app.api_client
+        .get(format!("{}/tags", &app.address))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let es6_id = tags.as_array().unwrap().iter().find(|t| t["name"] == "es6").unwrap()["id"].clone();
+
+    let quiz_id = quiz["id"].as_str().unwrap();
+    let updated: serde_json::Value = app.api_client
+        .put(format!("{}/quizzes/{}", &app.address, quiz_id))
+        .json(&serde_json::json!({ "tags": ["ES6", "Closures", "closures"] }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(updated["tags"], serde_json::json!(["es6", "closures"]));
+
+    // The tag keeps its ID and filters accept any spelling
+    let tags: serde_json::Value = app.api_client
+        .get(format!("{}/tags", &app.address))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let es6 = tags.as_array().unwrap().iter().find(|t| t["name"] == "es6").unwrap();
+    assert_eq!(es6["id"], es6_id);
+    assert_eq!(list_titles(&app, "tags=ES2015").await, ["Modules"]);
+}
+
+#[tokio::test]
+async fn tags_no_quiz_uses_are_forgotten() {
+    let app = spawn_app().await;
+    let tag_id = |tags: &serde_json::Value, name: &str| {
+        tags.as_array().unwrap().iter().find(|t| t["name"] == name).map(|t| t["id"].clone())
+    };
+    let list_tags = || async {
+        app.api_client
+            .get(format!("{}/tags", &app.address))
+            .send()
+            .await
+            .unwrap()
+            .json::<serde_json::Value>()
+            .await
+            .unwrap()
+    };
+    let quiz = common::quiz("Dom").tags(&["dom"]).questions(1).create(&app).await;
+    let dom_id = tag_id(&list_tags().await, "dom").unwrap();
+
+    let response = app.api_client.delete(format!("{}/quizzes/{}", &app.address, quiz.id)).send().await.unwrap();
+    assert_eq!(204, response.status().as_u16());
+    assert_eq!(tag_id(&list_tags().await, "dom"), None);
+
+    common::quiz("Dom again").tags(&["dom"]).questions(1).create(&app).await;
+    assert_ne!(tag_id(&list_tags().await, "dom").unwrap(), dom_id);
+}
@@ -0,0 +1,191 @@
+use crate::common::spawn_app;
+
+mod common;
+
+fn fields(json: &serde_json::Value) -> Vec<&str> {
+    json["fields"].as_array().unwrap().iter().map(|f| f["field"].as_str().unwrap()).collect()
+}
+
+#[tokio::test]
+async fn invalid_quiz_lists_every_field_and_is_not_stored() {
+    let app = spawn_app().await;
+
+    let response = app.api_client
+        .post(format!("{}/quizzes", &app.address))
+        .json(&serde_json::json!({
+            "title": "",
+            "tags": ["rust", "   ", "x".repeat(51)],
+            "questions": [
+                { "text": "Fine?", "options": [{ "text": "Yes", "is_correct": true }] },
+                { "text": "Empty?", "options": [] },
+                { "text": "", "hints": [""], "options": [{ "text": "", "is_correct": false }] }
+            ]
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(422, response.status().as_u16());
+    let json: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(json["error"], "Validation failed");
+    assert_eq!(fields(&json), [
+        "title",
+        "tags[2]",
+        "questions[1].options",
+        "questions[2].text",
+        "questions[2].hints[0]",
+        "questions[2].options",
+        "questions[2].options[0].text",
+    ]);
+    assert_eq!(json["fields"][2]["message"], "must have at least one option");
+
+    let listing: serde_json::Value = app.api_client
+        .get(format!("{}/quizzes", &app.address))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(listing["total"], 0);
+}
+
+#[tokio::test]
+async fn edits_cannot_leave_a_question_without_a_correct_option() {
+    let app = spawn_app().await;
+    let quiz: serde_json::Value = app.api_client
+        .post(format!("{}/quizzes", &app.address))
+        .json(&serde_json::json!({
+            "title": "Guarded",
+            "questions": [{
+                "text": "What is 2 + 2?",
+                "options": [
+                    { "text": "4", "is_correct": true },
+                    { "text": "5", "is_correct": false }
+                ]
+            }]
+        }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let quiz_id = quiz["id"].as_str().unwrap();
+    let question_id = quiz["questions"][0]["id"].as_str().unwrap();
+    let right = quiz["questions"][0]["options"][0]["id"].as_str().unwrap();
+    let option_url = format!("{}/quizzes/{}/questions/{}/options/{}", &app.address, quiz_id, question_id, right);
+
+    let response = app.api_client
+        .put(&option_url)
+        .json(&serde_json::json!({ "is_correct": false }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(422, response.status().as_u16());
+    let json: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(fields(&json), ["options"]);
+
+    let response = app.api_client.delete(&option_url).send().await.unwrap();
+    assert_eq!(422, response.status().as_u16());
+
+    let response = app.api_client
+        .post(format!("{}/quizzes/{}/questions", &app.address, quiz_id))
+        .json(&serde_json::json!({ "text": "No answers", "options": [] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(422, response.status().as_u16());
+
+    // The rejected edits changed nothing
+    let answer: serde_json::Value = app.api_client
+        .post(format!("{}/quizzes/{}/solve", &app.address, quiz_id))
+        .json(&serde_json::json!({ "question_id": question_id, "option_id": right }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(answer["correct"], true);
+
+    let response = app.api_client
+        .patch(format!("{}/quizzes/{}", &app.address, quiz_id))
+        .header("Content-Type", "application/json-patch+json")
+        .body(serde_json::json!([{ "op": "replace", "path": "/questions/0/options/0/is_correct", "value": false }]).to_string())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(422, response.status().as_u16());
+    let json: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(fields(&json), ["questions[0].options"]);
+}
+
+#[tokio::test]
+async fn learner_requests_are_validated() {
+    let app = spawn_app().await;
+
+    let response = app.api_client
+        .post(format!("{}/practice/sessions", &app.address))
+        .json(&serde_json::json!({ "user_email": " " }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(422, response.status().as_u16());
+
+    let response = app.api_client
+        .post(format!("{}/daily/attempts", &app.address))
+        .json(&serde_json::json!({ "user_email": "a@example.com", "tz": "Mars/Olympus" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(422, response.status().as_u16());
+    let json: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(fields(&json), ["tz"]);
+
+    let quiz = common::quiz("Ordered").create(&app).await;
+    let response = app.api_client
+        .put(format!("{}/quizzes/{}/questions/order", &app.address, quiz.id))
+        .json(&serde_json::json!({ "ids": [] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(422, response.status().as_u16());
+    let json: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(fields(&json), ["ids"]);
+}
+
+#[tokio::test]
+async fn bodies_and_queries_that_do_not_deserialize_are_field_errors() {
+    let app = spawn_app().await;
+
+    let response = app.api_client
+        .post(format!("{}/quizzes", &app.address))
+        .json(&serde_json::json!({ "title": 5, "questions": [] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(422, response.status().as_u16());
+    let json: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(json["error"], "Validation failed");
+    assert_eq!(fields(&json), ["body"]);
+    assert!(json["fields"][0]["message"].as_str().unwrap().starts_with("invalid type: integer `5`"));
+
+    let response = app.api_client
+        .get(format!("{}/me/progress", &app.address))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(422, response.status().as_u16());
+    let json: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(fields(&json), ["user_email"]);
+
+    // Bodies that aren't JSON at all are still bad requests
+    let response = app.api_client
+        .post(format!("{}/quizzes", &app.address))
+        .header("Content-Type", "application/json")
+        .body("{not json")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(400, response.status().as_u16());
+}
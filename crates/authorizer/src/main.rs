@@ -13,22 +13,29 @@ struct CustomAuthorizerRequest {
 
 #[derive(Serialize)]
 struct CustomAuthorizerResponse {
-    principalId: String,
-    policyDocument: PolicyDocument,
+    #[serde(rename = "principalId")]
+    principal_id: String,
+    #[serde(rename = "policyDocument")]
+    policy_document: PolicyDocument,
     context: Option<Value>,
 }
 
 #[derive(Serialize)]
 struct PolicyDocument {
-    Version: String,
-    Statement: Vec<Statement>,
+    #[serde(rename = "Version")]
+    version: String,
+    #[serde(rename = "Statement")]
+    statement: Vec<Statement>,
 }
 
 #[derive(Serialize)]
 struct Statement {
-    Action: String,
-    Effect: String,
-    Resource: String,
+    #[serde(rename = "Action")]
+    action: String,
+    #[serde(rename = "Effect")]
+    effect: String,
+    #[serde(rename = "Resource")]
+    resource: String,
 }
 
 #[tokio::main]
@@ -66,13 +73,13 @@ async fn handler(event: LambdaEvent<CustomAuthorizerRequest>) -> Result<CustomAu
 
 fn generate_policy(principal_id: &str, effect: &str, resource: &str) -> CustomAuthorizerResponse {
     CustomAuthorizerResponse {
-        principalId: principal_id.to_string(),
-        policyDocument: PolicyDocument {
-            Version: "2012-10-17".to_string(),
-            Statement: vec![Statement {
-                Action: "execute-api:Invoke".to_string(),
-                Effect: effect.to_string(),
-                Resource: resource.to_string(), 
+        principal_id: principal_id.to_string(),
+        policy_document: PolicyDocument {
+            version: "2012-10-17".to_string(),
+            statement: vec![Statement {
+                action: "execute-api:Invoke".to_string(),
+                effect: effect.to_string(),
+                resource: resource.to_string(), 
                 // Note: 'resource' here is the method ARN. In production you might want to wildcard this 
                 // to avoid cached policy issues if the ARN changes slightly (e.g. diff paths)
             }],